@@ -31,12 +31,37 @@ macro_rules! gen_uint {
     }
 }
 
+macro_rules! gen_bytes {
+    ($fnn:ident, $rng:ident) => {
+        #[bench]
+        fn $fnn(b: &mut Bencher) {
+            let mut rng = $rng::seed_from_u64(0);
+            let mut buf = [0u8; 1024];
+            b.bytes = buf.len() as u64 * RAND_BENCH_N;
+            b.iter(|| {
+                for _ in 0..RAND_BENCH_N {
+                    rng.fill_bytes(&mut buf);
+                    test::black_box(&buf);
+                }
+            });
+        }
+    }
+}
+
+// Reseeded from entropy after every `DEFAULT_RESEED_BUDGET` bytes.
+type ReseedingSfc64Rng = ReseedingSmallRng<Sfc64Rng>;
+
+type Xorshift128PlusXorWeylRng =
+    CombinedRng<Xorshift128PlusRng, WeylRng, CombineXor>;
+
 gen_uint!(gen_u32_ci, next_u32, CiRng);
 gen_uint!(gen_u32_gj, next_u32, GjRng);
 gen_uint!(gen_u32_jsf32, next_u32, Jsf32Rng);
 gen_uint!(gen_u32_jsf64, next_u32, Jsf64Rng);
 gen_uint!(gen_u32_kiss32, next_u32, Kiss32Rng);
 gen_uint!(gen_u32_kiss64, next_u32, Kiss64Rng);
+gen_uint!(gen_u32_lfsr113, next_u32, Lfsr113Rng);
+gen_uint!(gen_u32_lfsr258, next_u32, Lfsr258Rng);
 gen_uint!(gen_u32_msws, next_u32, MswsRng);
 gen_uint!(gen_u32_mwp, next_u32, MwpRng);
 gen_uint!(gen_u32_pcg_xsh_64_lcg, next_u32, PcgXsh64LcgRng);
@@ -46,18 +71,27 @@ gen_uint!(gen_u32_sapparoth_32, next_u32, Sapparot32Rng);
 gen_uint!(gen_u32_sapparoth_64, next_u32, Sapparot64Rng);
 gen_uint!(gen_u32_sfc_32, next_u32, Sfc32Rng);
 gen_uint!(gen_u32_sfc_64, next_u32, Sfc64Rng);
+#[cfg(feature = "simd")]
+gen_uint!(gen_u32_sfc_64x4, next_u32, Sfc64x4Rng);
 gen_uint!(gen_u32_velox, next_u32, Velox3bRng);
+gen_uint!(gen_u32_xorshift_32, next_u32, Xorshift32Rng);
+gen_uint!(gen_u32_xorshift_64, next_u32, Xorshift64Rng);
 gen_uint!(gen_u32_xorshift_128_32, next_u32, Xorshift128_32Rng);
 gen_uint!(gen_u32_xorshift_128_64, next_u32, Xorshift128_64Rng);
 gen_uint!(gen_u32_xorshift_128_plus, next_u32, Xorshift128PlusRng);
 gen_uint!(gen_u32_xorshift_mt_32, next_u32, XorshiftMt32Rng);
 gen_uint!(gen_u32_xorshift_mt_64, next_u32, XorshiftMt64Rng);
 gen_uint!(gen_u32_xoroshiro_128_plus, next_u32, Xoroshiro128PlusRng);
+gen_uint!(gen_u32_xoroshiro_128_plusplus, next_u32, Xoroshiro128PlusPlusRng);
+gen_uint!(gen_u32_xoroshiro_128_starstar, next_u32, Xoroshiro128StarStarRng);
 gen_uint!(gen_u32_xoroshiro_64_plus, next_u32, Xoroshiro64PlusRng);
+gen_uint!(gen_u32_xoroshiro_64_starstar, next_u32, Xoroshiro64StarStarRng);
 gen_uint!(gen_u32_xoroshiro_mt_64of128, next_u32, XoroshiroMt64of128Rng);
 gen_uint!(gen_u32_xoroshiro_mt_32of128, next_u32, XoroshiroMt32of128Rng);
 gen_uint!(gen_u32_xsm32, next_u32, Xsm32Rng);
 gen_uint!(gen_u32_xsm64, next_u32, Xsm64Rng);
+#[cfg(feature = "simd")]
+gen_uint!(gen_u32_xoshiro_256x4, next_u32, Xoshiro256x4Rng);
 
 gen_uint!(gen_u64_ci, next_u64, CiRng);
 gen_uint!(gen_u64_gj, next_u64, GjRng);
@@ -65,24 +99,50 @@ gen_uint!(gen_u64_jsf32, next_u64, Jsf32Rng);
 gen_uint!(gen_u64_jsf64, next_u64, Jsf64Rng);
 gen_uint!(gen_u64_kiss32, next_u64, Kiss32Rng);
 gen_uint!(gen_u64_kiss64, next_u64, Kiss64Rng);
+gen_uint!(gen_u64_lfsr113, next_u64, Lfsr113Rng);
+gen_uint!(gen_u64_lfsr258, next_u64, Lfsr258Rng);
 gen_uint!(gen_u64_msws, next_u64, MswsRng);
 gen_uint!(gen_u64_mwp, next_u64, MwpRng);
 gen_uint!(gen_u64_sapparoth_32, next_u64, Sapparot32Rng);
 gen_uint!(gen_u64_sapparoth_64, next_u64, Sapparot64Rng);
 gen_uint!(gen_u64_sfc_32, next_u64, Sfc32Rng);
 gen_uint!(gen_u64_sfc_64, next_u64, Sfc64Rng);
+gen_uint!(gen_u64_sfc_64_reseeding, next_u64, ReseedingSfc64Rng);
+#[cfg(feature = "simd")]
+gen_uint!(gen_u64_sfc_64x4, next_u64, Sfc64x4Rng);
 gen_uint!(gen_u64_pcg_xsh_64_lcg, next_u64, PcgXsh64LcgRng);
 gen_uint!(gen_u64_pcg_xsl_64_lcg, next_u64, PcgXsl64LcgRng);
 gen_uint!(gen_u64_pcg_xsl_128_mcg, next_u64, PcgXsl128McgRng);
 gen_uint!(gen_u64_velox, next_u64, Velox3bRng);
+gen_uint!(gen_u64_weyl, next_u64, WeylRng);
+gen_uint!(gen_u64_xorshift_32, next_u64, Xorshift32Rng);
+gen_uint!(gen_u64_xorshift_64, next_u64, Xorshift64Rng);
 gen_uint!(gen_u64_xorshift_128_32, next_u64, Xorshift128_32Rng);
 gen_uint!(gen_u64_xorshift_128_64, next_u64, Xorshift128_64Rng);
 gen_uint!(gen_u64_xorshift_128_plus, next_u64, Xorshift128PlusRng);
+gen_uint!(gen_u64_xorshift_128_plus_xor_weyl, next_u64, Xorshift128PlusXorWeylRng);
 gen_uint!(gen_u64_xorshift_mt_32, next_u64, XorshiftMt32Rng);
 gen_uint!(gen_u64_xorshift_mt_64, next_u64, XorshiftMt64Rng);
 gen_uint!(gen_u64_xoroshiro_128_plus, next_u64, Xoroshiro128PlusRng);
+gen_uint!(gen_u64_xoroshiro_128_plusplus, next_u64, Xoroshiro128PlusPlusRng);
+gen_uint!(gen_u64_xoroshiro_128_starstar, next_u64, Xoroshiro128StarStarRng);
 gen_uint!(gen_u64_xoroshiro_64_plus, next_u64, Xoroshiro64PlusRng);
+gen_uint!(gen_u64_xoroshiro_64_starstar, next_u64, Xoroshiro64StarStarRng);
 gen_uint!(gen_u64_xoroshiro_mt_64of128, next_u64, XoroshiroMt64of128Rng);
 gen_uint!(gen_u64_xoroshiro_mt_32of128, next_u64, XoroshiroMt32of128Rng);
 gen_uint!(gen_u64_xsm32, next_u64, Xsm32Rng);
 gen_uint!(gen_u64_xsm64, next_u64, Xsm64Rng);
+#[cfg(feature = "simd")]
+gen_uint!(gen_u64_xoshiro_256x4, next_u64, Xoshiro256x4Rng);
+
+gen_uint!(gen_u128_gj, next_u128, GjRng);
+gen_uint!(gen_u128_pcg_xsl_128_mcg, next_u128, PcgXsl128McgRng);
+gen_uint!(gen_u128_pcg_xsl_64_lcg, next_u128, PcgXsl64LcgRng);
+gen_uint!(gen_u128_sfc_64, next_u128, Sfc64Rng);
+gen_uint!(gen_u128_xsm64, next_u128, Xsm64Rng);
+
+gen_bytes!(gen_bytes_sfc_64, Sfc64Rng);
+#[cfg(feature = "simd")]
+gen_bytes!(gen_bytes_sfc_64x4, Sfc64x4Rng);
+#[cfg(feature = "simd")]
+gen_bytes!(gen_bytes_xoshiro_256x4, Xoshiro256x4Rng);
@@ -0,0 +1,71 @@
+#ifndef SMALL_RNGS_H
+#define SMALL_RNGS_H
+
+/* Generated with cbindgen from src/ffi.rs, do not edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * An opaque handle to a generator.
+ */
+typedef struct RngHandle RngHandle;
+
+/**
+ * Create the generator with the given name.
+ *
+ * The seed must be exactly as long as returned by `small_rngs_seed_len`. If
+ * `seed_ptr` is null the generator is seeded with entropy, from the operating
+ * system or else from the function registered with
+ * `entropy::seed_from_hardware`.
+ *
+ * Returns null if the name is unknown, the seed has the wrong length, the
+ * generator rejects the seed, or no source of entropy is available.
+ *
+ * # Safety
+ *
+ * `name` must be null or a nul-terminated string, and `seed_ptr` must be
+ * null or point to `seed_len` readable bytes.
+ */
+struct RngHandle *small_rngs_new(const char *name, const uint8_t *seed_ptr, size_t seed_len);
+
+/**
+ * The seed length in bytes of the generator with the given name, or 0 if
+ * the name is unknown.
+ *
+ * # Safety
+ *
+ * `name` must be null or a nul-terminated string.
+ */
+size_t small_rngs_seed_len(const char *name);
+
+/**
+ * Return the next 64 bits of output.
+ *
+ * # Safety
+ *
+ * `rng` must be a valid handle returned by `small_rngs_new`.
+ */
+uint64_t small_rngs_next_u64(struct RngHandle *rng);
+
+/**
+ * Fill `len` bytes at `buf` with output.
+ *
+ * # Safety
+ *
+ * `rng` must be a valid handle returned by `small_rngs_new`, and `buf` must
+ * point to `len` writable bytes.
+ */
+void small_rngs_fill(struct RngHandle *rng, uint8_t *buf, size_t len);
+
+/**
+ * Free a generator. Does nothing if `rng` is null.
+ *
+ * # Safety
+ *
+ * `rng` must be null or a handle returned by `small_rngs_new` that is not
+ * yet freed.
+ */
+void small_rngs_free(struct RngHandle *rng);
+
+#endif  /* SMALL_RNGS_H */
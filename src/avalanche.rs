@@ -0,0 +1,192 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Avalanche analysis of seeding and output functions.
+//!
+//! For a good mixing function, flipping a single input bit flips every output
+//! bit with probability 1/2. This measures, for every pair of input and output
+//! bits, how often the output bit flips over many random inputs.
+
+use rand_core::RngCore;
+use core::mem::size_of;
+use std::vec;
+use std::vec::Vec;
+
+#[cfg(feature = "pcg")]
+use crate::pcg::{PcgOutput, PcgWord};
+use crate::registry::RngEntry;
+
+/// How often each output bit flips when an input bit is flipped.
+#[derive(Clone, Debug)]
+pub struct AvalancheMatrix {
+    /// Number of input bits.
+    pub in_bits: usize,
+    /// Number of output bits.
+    pub out_bits: usize,
+    /// Number of random inputs.
+    pub samples: u64,
+    // Number of flips, indexed by `input bit * out_bits + output bit`.
+    flips: Vec<u64>,
+}
+
+impl AvalancheMatrix {
+    /// Probability that flipping input bit `i` flips output bit `j`.
+    pub fn probability(&self, i: usize, j: usize) -> f64 {
+        self.flips[i * self.out_bits + j] as f64 / self.samples as f64
+    }
+
+    /// Bias of output bit `j` for input bit `i`, from -1.0 (never flips) to
+    /// 1.0 (always flips). Ideally 0.0.
+    pub fn bias(&self, i: usize, j: usize) -> f64 {
+        2.0 * self.probability(i, j) - 1.0
+    }
+
+    fn biases<'a>(&'a self) -> impl Iterator<Item = f64> + 'a {
+        self.flips.iter()
+            .map(move |&n| 2.0 * n as f64 / self.samples as f64 - 1.0)
+    }
+
+    /// Largest absolute bias.
+    pub fn max_bias(&self) -> f64 {
+        self.biases().fold(0.0, |max, b| b.abs().max(max))
+    }
+
+    /// Mean absolute bias.
+    pub fn mean_bias(&self) -> f64 {
+        self.biases().map(f64::abs).sum::<f64>() / self.flips.len() as f64
+    }
+
+    /// Root mean square of the bias.
+    pub fn rms_bias(&self) -> f64 {
+        (self.biases().map(|b| b * b).sum::<f64>() / self.flips.len() as f64)
+            .sqrt()
+    }
+
+    /// The standard deviation of the bias of an ideal mixing function for this
+    /// number of samples. Biases of a few times this size are just noise.
+    pub fn noise(&self) -> f64 {
+        1.0 / (self.samples as f64).sqrt()
+    }
+}
+
+/// Measure the avalanche of `f`, which maps an input of `input_len` bytes to
+/// an output of `out_bits` bits. The inputs are drawn from `rng`.
+pub fn measure<F>(input_len: usize, out_bits: u32, samples: u64,
+                  rng: &mut dyn RngCore, f: F) -> AvalancheMatrix
+    where F: FnMut(&[u8]) -> u64
+{
+    measure_valid(input_len, out_bits, samples, rng, |_| true, f)
+}
+
+// Like `measure`, but only for inputs for which `valid` holds, also after
+// flipping any one bit. Other inputs are drawn again.
+fn measure_valid<V, F>(input_len: usize, out_bits: u32, samples: u64,
+                       rng: &mut dyn RngCore, mut valid: V, mut f: F)
+    -> AvalancheMatrix
+    where V: FnMut(&[u8]) -> bool, F: FnMut(&[u8]) -> u64
+{
+    let in_bits = input_len * 8;
+    let out_bits = out_bits as usize;
+    let mut flips = vec![0u64; in_bits * out_bits];
+    let mut input = vec![0u8; input_len];
+
+    for _ in 0..samples {
+        loop {
+            rng.fill_bytes(&mut input);
+            if valid(&input) && (0..in_bits).all(|i| {
+                input[i / 8] ^= 1 << (i % 8);
+                let ok = valid(&input);
+                input[i / 8] ^= 1 << (i % 8);
+                ok
+            }) {
+                break;
+            }
+        }
+        let base = f(&input);
+        for i in 0..in_bits {
+            input[i / 8] ^= 1 << (i % 8);
+            let delta = base ^ f(&input);
+            input[i / 8] ^= 1 << (i % 8);
+
+            let row = &mut flips[i * out_bits..(i + 1) * out_bits];
+            for (j, count) in row.iter_mut().enumerate() {
+                *count += (delta >> j) & 1;
+            }
+        }
+    }
+    AvalancheMatrix { in_bits, out_bits, samples, flips }
+}
+
+/// Avalanche from the seed of a generator to its first 64-bit output.
+///
+/// Seeds the generator rejects, like weak seeds of `MswsRng`, are skipped:
+/// a seed is only used if it and every seed one bit flip away are valid.
+pub fn seed(entry: &RngEntry, samples: u64, rng: &mut dyn RngCore)
+    -> AvalancheMatrix
+{
+    measure_valid(entry.seed_len(), 64, samples, rng,
+                  |seed| entry.is_valid_seed(seed),
+                  |seed| entry.from_seed(seed).unwrap().next_u64())
+}
+
+/// A word that can be the input of an output function.
+pub trait InputWord: Copy {
+    /// Construct the word from `size_of::<Self>()` little-endian bytes.
+    fn from_le_slice(bytes: &[u8]) -> Self;
+}
+
+macro_rules! input_word {
+    ($ty:ty) => {
+        impl InputWord for $ty {
+            fn from_le_slice(bytes: &[u8]) -> Self {
+                let mut buf = [0u8; size_of::<$ty>()];
+                buf.copy_from_slice(bytes);
+                <$ty>::from_le_bytes(buf)
+            }
+        }
+    }
+}
+
+input_word!(u32);
+input_word!(u64);
+input_word!(u128);
+
+/// Avalanche of a PCG output function, from the state to the output word.
+#[cfg(feature = "pcg")]
+pub fn output_function<T, O>(samples: u64, rng: &mut dyn RngCore)
+    -> AvalancheMatrix
+    where T: InputWord, O: PcgOutput<T>
+{
+    measure(size_of::<T>(), O::Output::BITS, samples, rng, |state| {
+        O::output(T::from_le_slice(state)).to_u64()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::Words;
+    use crate::registry;
+    use super::*;
+
+    // The first two seeds are skipped: in the first bytes 4..8 are zero, and
+    // in the second they become zero when their one set bit is flipped.
+    #[cfg(feature = "msws")]
+    #[test]
+    fn seed_skips_invalid_seeds() {
+        let entry = registry::find("msws").unwrap();
+        let mut rng = Words(&[0, 1, 1 << 32, 2, 0x0303_0303_0303_0303, 3]);
+        let matrix = seed(entry, 1, &mut rng);
+
+        let mut rng = Words(&[0x0303_0303_0303_0303, 3]);
+        let expected = measure(16, 64, 1, &mut rng, |seed| {
+            entry.from_seed(seed).unwrap().next_u64()
+        });
+        assert_eq!(matrix.flips, expected.flips);
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Measure the avalanche of seeding and of the PCG output functions.
+
+extern crate small_rngs;
+extern crate rand_core;
+
+use small_rngs::avalanche::{self, AvalancheMatrix};
+use small_rngs::registry;
+use small_rngs::*;
+use std::env;
+use std::process;
+
+const FUNCTIONS: &[&str] = &["dxsm_128_64", "rxs_m_xs_32_32", "rxs_m_xs_64_64",
+                             "xsh_rr_64_32", "xsl_rr_128_64", "xsl_rr_64_32"];
+
+fn print_usage(cmd: &str, names: Vec<String>) {
+    println!("Usage: {} seed RNG [SAMPLES]
+       {} output FUNCTION [SAMPLES]
+where RNG is one of: {:?}
+and FUNCTION is one of: {:?}
+
+Flips every bit of the seed of an RNG (compared against the first 64-bit
+output), or of the state of a PCG output function, for SAMPLES random inputs
+(default 10000). Prints a map of the bias of every input/output bit pair and
+summary statistics.",
+        cmd, cmd, names, FUNCTIONS);
+}
+
+fn main() {
+    let names: Vec<String> = registry::GENERATORS.iter()
+                                 .map(|entry| String::from(entry.name))
+                                 .collect();
+
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 || args.len() > 4 {
+        print_usage(&args[0], names);
+        process::exit(1);
+    }
+    let samples = match args.get(3).map(|s| s.parse()) {
+        None => 10000,
+        Some(Ok(samples)) => samples,
+        Some(Err(_)) => {
+            println!("Error: invalid number of samples: {}", args[3]);
+            process::exit(1);
+        }
+    };
+
+    let mut rng = entropy::from_entropy::<Sfc64Rng>();
+    let matrix = match (args[1].as_str(), args[2].as_str()) {
+        ("seed", name) => match registry::find(name) {
+            Some(entry) => avalanche::seed(entry, samples, &mut rng),
+            None => {
+                println!("Error: unknown RNG: {}", name);
+                println!();
+                print_usage(&args[0], names);
+                process::exit(1);
+            }
+        },
+        ("output", "dxsm_128_64") =>
+            avalanche::output_function::<u128, Dxsm>(samples, &mut rng),
+        ("output", "rxs_m_xs_32_32") =>
+            avalanche::output_function::<u32, RxsMXs>(samples, &mut rng),
+        ("output", "rxs_m_xs_64_64") =>
+            avalanche::output_function::<u64, RxsMXs>(samples, &mut rng),
+        ("output", "xsh_rr_64_32") =>
+            avalanche::output_function::<u64, XshRr>(samples, &mut rng),
+        ("output", "xsl_rr_128_64") =>
+            avalanche::output_function::<u128, XslRr>(samples, &mut rng),
+        ("output", "xsl_rr_64_32") =>
+            avalanche::output_function::<u64, XslRr>(samples, &mut rng),
+        _ => {
+            println!("Error: unknown mode or function: {} {}", args[1], args[2]);
+            println!();
+            print_usage(&args[0], names);
+            process::exit(1);
+        }
+    };
+    print_map(&matrix);
+    print_summary(&matrix);
+}
+
+// One character per input/output bit pair, by the absolute bias.
+fn print_map(matrix: &AvalancheMatrix) {
+    println!("input bit down, output bit across (bit 0 first); absolute bias:
+' ' < 1/32 <= '.' < 1/16 <= '-' < 1/8 <= '+' < 1/4 <= '*' < 1/2 <= '#'");
+    for i in 0..matrix.in_bits {
+        let row: String = (0..matrix.out_bits).map(|j| {
+            match matrix.bias(i, j).abs() {
+                b if b >= 0.5 => '#',
+                b if b >= 0.25 => '*',
+                b if b >= 0.125 => '+',
+                b if b >= 0.0625 => '-',
+                b if b >= 0.03125 => '.',
+                _ => ' ',
+            }
+        }).collect();
+        println!("{:>4} |{}|", i, row);
+    }
+}
+
+fn print_summary(matrix: &AvalancheMatrix) {
+    println!("{} input bits, {} output bits, {} samples",
+             matrix.in_bits, matrix.out_bits, matrix.samples);
+    println!("max |bias|:  {:.5}", matrix.max_bias());
+    println!("mean |bias|: {:.5}", matrix.mean_bias());
+    println!("rms bias:    {:.5}", matrix.rms_bias());
+    println!("noise level: {:.5} (rms bias of an ideal function)",
+             matrix.noise());
+}
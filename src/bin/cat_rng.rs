@@ -13,71 +13,88 @@
 extern crate small_rngs;
 extern crate rand_core;
 
-use rand_core::{RngCore, SeedableRng};
-use small_rngs::*;
-use std::collections::HashMap;
+use rand_core::RngCore;
+use small_rngs::{registry, Transform, TransformRng};
 use std::env;
 use std::io::{self, Write, Error};
 use std::iter::Iterator;
 
 fn print_usage(cmd: &String, names: Vec<String>) {
-    println!("Usage: {} RNG
+    println!("Usage: {} [OPTIONS] RNG
 where RNG is one of: {:?}
 
 This is a small tool to endlessly contatenate output from an RNG. It can for
-example be used with PractRand: ./cat_rng jsf32 | RNG_test stdin -multithreaded",
+example be used with PractRand: ./cat_rng jsf32 | RNG_test stdin -multithreaded
+
+Options to transform each output word, to test the other end of the words:
+    --byteswap       reverse the order of the bytes
+    --bitreverse     reverse the order of the bits
+    --complement     invert all bits
+    --word-bits N    word size of the transforms, 32 or 64 (default: the word
+                     size of the RNG, and 32 for RNGs with smaller words)",
         cmd, names);
 }
 
-type BR = Box<dyn RngCore>;
-
 fn main() {
-    let mut ctors: HashMap<&'static str,
-            &dyn Fn() -> BR> = HashMap::new();
-    ctors.insert("ci", &|| Box::new(CiRng::from_entropy()));
-    ctors.insert("gj", &|| Box::new(GjRng::from_entropy()));
-    ctors.insert("jsf32", &|| Box::new(Jsf32Rng::from_entropy()));
-    ctors.insert("jsf64", &|| Box::new(Jsf64Rng::from_entropy()));
-    ctors.insert("kiss32", &|| Box::new(Kiss32Rng::from_entropy()));
-    ctors.insert("kiss64", &|| Box::new(Kiss64Rng::from_entropy()));
-    ctors.insert("msws", &|| Box::new(MswsRng::from_entropy()));
-    ctors.insert("mwp", &|| Box::new(MwpRng::from_entropy()));
-    ctors.insert("pcg_xsh_64_lcg", &|| Box::new(PcgXsh64LcgRng::from_entropy()));
-    ctors.insert("pcg_xsl_64_lcg", &|| Box::new(PcgXsl64LcgRng::from_entropy()));
-    ctors.insert("pcg_xsl_128_mcg", &|| Box::new(PcgXsl128McgRng::from_entropy()));
-    ctors.insert("sapparoth_32", &|| Box::new(Sapparot32Rng::from_entropy()));
-    ctors.insert("sapparoth_64", &|| Box::new(Sapparot64Rng::from_entropy()));
-    ctors.insert("sfc_32", &|| Box::new(Sfc32Rng::from_entropy()));
-    ctors.insert("sfc_64", &|| Box::new(Sfc64Rng::from_entropy()));
-    ctors.insert("velox", &|| Box::new(Velox3bRng::from_entropy()));
-    ctors.insert("xorshift_128_32", &|| Box::new(Xorshift128_32Rng::from_entropy()));
-    ctors.insert("xorshift_128_64", &|| Box::new(Xorshift128_64Rng::from_entropy()));
-    ctors.insert("xorshift_128_plus", &|| Box::new(Xorshift128PlusRng::from_entropy()));
-    ctors.insert("xorshift_mt_32", &|| Box::new(XorshiftMt32Rng::from_entropy()));
-    ctors.insert("xorshift_mt_64", &|| Box::new(XorshiftMt64Rng::from_entropy()));
-    ctors.insert("xoroshiro_128_plus", &|| Box::new(Xoroshiro128PlusRng::from_entropy()));
-    ctors.insert("xoroshiro_64_plus", &|| Box::new(Xoroshiro64PlusRng::from_entropy()));
-    ctors.insert("xoroshiro_mt_64of128", &|| Box::new(XoroshiroMt64of128Rng::from_entropy()));
-    ctors.insert("xoroshiro_mt_32of128", &|| Box::new(XoroshiroMt32of128Rng::from_entropy()));
-    ctors.insert("xsm32", &|| Box::new(Xsm32Rng::from_entropy()));
-    ctors.insert("xsm64", &|| Box::new(Xsm64Rng::from_entropy()));
+    let names: Vec<String> = registry::GENERATORS.iter()
+                                 .map(|entry| String::from(entry.name))
+                                 .collect();
 
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        print_usage(&args[0], ctors.keys().map(|s| String::from(*s)).collect());
-    } else {
-        if let Some(ctor) = ctors.get(&*args[1]) {
-            let rng = ctor();
-            cat_rng(rng).unwrap();
+    let mut transform = Transform::default();
+    let mut word_bits = None;
+    let mut name = None;
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--byteswap" => transform.byte_swap = true,
+            "--bitreverse" => transform.bit_reverse = true,
+            "--complement" => transform.complement = true,
+            "--word-bits" => {
+                i += 1;
+                match args.get(i).map(|s| s.as_str()) {
+                    Some("32") => word_bits = Some(32),
+                    Some("64") => word_bits = Some(64),
+                    _ => {
+                        println!("Error: --word-bits must be 32 or 64");
+                        return;
+                    }
+                }
+            }
+            arg if name.is_none() && !arg.starts_with("--") => name = Some(arg),
+            arg => {
+                println!("Error: unexpected argument: {}", arg);
+                println!();
+                print_usage(&args[0], names);
+                return;
+            }
+        }
+        i += 1;
+    }
+
+    if let Some(name) = name {
+        if let Some(entry) = registry::find(name) {
+            let mut rng = entry.from_entropy();
+            if transform == Transform::default() {
+                cat_rng(&mut *rng).unwrap();
+            } else {
+                // The 8- and 16-bit generators fill 32-bit words.
+                let word_bits = word_bits.unwrap_or(entry.word_bits.max(32));
+                let rng = TransformRng::new(&mut *rng, transform)
+                              .with_word_bits(word_bits);
+                cat_rng(rng).unwrap();
+            }
         } else {
-            println!("Error: unknown RNG: {}", args[1]);
+            println!("Error: unknown RNG: {}", name);
             println!();
-            print_usage(&args[0], ctors.keys().map(|s| String::from(*s)).collect());
+            print_usage(&args[0], names);
         }
+    } else {
+        print_usage(&args[0], names);
     }
 }
 
-fn cat_rng(mut rng: Box<dyn RngCore>) -> Result<(), Error> {
+fn cat_rng<R: RngCore>(mut rng: R) -> Result<(), Error> {
     let mut buf =  [0u8; 32];
     let stdout = io::stdout();
     let mut lock = stdout.lock();
@@ -0,0 +1,175 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Analyze the cycle structure of the scaled-down chaotic generators.
+
+extern crate small_rngs;
+extern crate rand_core;
+
+use rand_core::RngCore;
+use small_rngs::cycles::{self, CycleState};
+use small_rngs::*;
+use std::collections::BTreeMap;
+use std::env;
+use std::process;
+
+const NAMES: &[&str] = &["gj8", "gj16", "jsf8", "jsf16", "sfc_8", "sfc_16"];
+
+fn print_usage(cmd: &str) {
+    println!("Usage: {} RNG [--samples N] [--max-log2 N] [--short-log2 N]
+where RNG is one of: {:?}
+
+Generators with a 32-bit state are analyzed exhaustively, which needs 512 MiB
+of memory. For larger states the cycle length is measured from N random
+starting states (default 100) with Brent's algorithm, giving up after
+2^max-log2 steps (default 32).
+
+Cycles shorter than 2^short-log2 are reported as short (default: half the
+number of state bits). Both limits can be at most the number of state bits.",
+        cmd, NAMES);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 || !args.len().is_multiple_of(2) {
+        print_usage(&args[0]);
+        process::exit(1);
+    }
+
+    let mut samples = 100;
+    let mut max_log2 = 32;
+    let mut short_log2 = None;
+    for option in args[2..].chunks(2) {
+        let value = match option[1].parse() {
+            Ok(value) => value,
+            Err(_) => {
+                println!("Error: invalid number: {}", option[1]);
+                process::exit(1);
+            }
+        };
+        match option[0].as_str() {
+            "--samples" => samples = value,
+            "--max-log2" => max_log2 = value,
+            "--short-log2" => short_log2 = Some(value),
+            _ => {
+                println!("Error: unknown option: {}", option[0]);
+                println!();
+                print_usage(&args[0]);
+                process::exit(1);
+            }
+        }
+    }
+
+    match args[1].as_str() {
+        "gj8" => analyze::<Gj8Rng>(samples, max_log2, short_log2),
+        "gj16" => analyze::<Gj16Rng>(samples, max_log2, short_log2),
+        "jsf8" => analyze::<Jsf8Rng>(samples, max_log2, short_log2),
+        "jsf16" => analyze::<Jsf16Rng>(samples, max_log2, short_log2),
+        "sfc_8" => analyze::<Sfc8Rng>(samples, max_log2, short_log2),
+        "sfc_16" => analyze::<Sfc16Rng>(samples, max_log2, short_log2),
+        _ => {
+            println!("Error: unknown RNG: {}", args[1]);
+            println!();
+            print_usage(&args[0]);
+            process::exit(1);
+        }
+    }
+}
+
+fn analyze<R: CycleState>(samples: u64, max_log2: u64, short_log2: Option<u64>) {
+    let state_bits = u64::from(R::STATE_BITS);
+    let short_log2 = short_log2.unwrap_or(state_bits / 2);
+    if max_log2 > state_bits || short_log2 > state_bits {
+        println!("Error: --max-log2 and --short-log2 can be at most {}, the \
+                  number of state bits", state_bits);
+        process::exit(1);
+    }
+    let (max_log2, short_log2) = (max_log2 as u32, short_log2 as u32);
+    if R::STATE_BITS <= 32 {
+        exhaustive::<R>(short_log2);
+    } else {
+        sampled::<R>(samples, max_log2, short_log2);
+    }
+}
+
+fn log2(x: u64) -> u32 {
+    63 - x.leading_zeros()
+}
+
+// 2^log2, saturated to `u64::MAX` for a 64-bit state.
+fn pow2(log2: u32) -> u64 {
+    1u64.checked_shl(log2).unwrap_or(u64::MAX)
+}
+
+fn exhaustive<R: CycleState>(short_log2: u32) {
+    println!("Exhaustive search of all 2^{} states", R::STATE_BITS);
+    let counts = match cycles::exhaustive::<R>() {
+        Some(counts) => counts,
+        None => {
+            println!("The state transition is not a permutation");
+            return;
+        }
+    };
+
+    let mut buckets = BTreeMap::new();
+    for (&len, &count) in &counts.cycles {
+        let bucket = buckets.entry(log2(len)).or_insert((0, 0));
+        bucket.0 += count;
+        bucket.1 += len * count;
+    }
+    println!("cycle length   cycles       states");
+    for (bits, (cycles, states)) in buckets {
+        println!("2^{:<2}         {:>10}   {:>10} ({:.3e})",
+                 bits, cycles, states,
+                 states as f64 / (R::STATE_BITS as f64).exp2());
+    }
+
+    println!("cycles: {}", counts.total_cycles());
+    println!("shortest cycle: {}", counts.min_length().unwrap());
+    println!("longest cycle: {}", counts.max_length().unwrap());
+    println!("fraction of states on cycles shorter than 2^{}: {:.3e}",
+             short_log2, counts.fraction_below(pow2(short_log2)));
+}
+
+fn sampled<R: CycleState>(samples: u64, max_log2: u32, short_log2: u32) {
+    println!("Brent's algorithm from {} random states, at most 2^{} steps",
+             samples, max_log2);
+    let mut rng = entropy::from_entropy::<Sfc64Rng>();
+    let mask = !0u64 >> (64 - R::STATE_BITS);
+
+    let mut buckets = BTreeMap::new();
+    let mut unfinished = 0;
+    let mut shortest = None;
+    let mut short = 0;
+    for _ in 0..samples {
+        match cycles::brent::<R>(rng.next_u64() & mask, pow2(max_log2)) {
+            Some(len) => {
+                *buckets.entry(log2(len)).or_insert(0) += 1;
+                shortest = Some(shortest.map_or(len, |s: u64| s.min(len)));
+                if len < pow2(short_log2) {
+                    short += 1;
+                }
+            }
+            None => unfinished += 1,
+        }
+    }
+
+    println!("cycle length   samples");
+    for (bits, count) in buckets {
+        println!("2^{:<2}         {:>7}", bits, count);
+    }
+    println!("not found      {:>7}", unfinished);
+
+    match shortest {
+        Some(len) => println!("shortest cycle found: {}", len),
+        None => println!("no cycle found"),
+    }
+    println!("fraction of sampled states on cycles shorter than 2^{}: {}/{}",
+             short_log2, short, samples);
+}
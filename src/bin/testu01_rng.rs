@@ -0,0 +1,75 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Run one of the TestU01 batteries on an RNG.
+
+extern crate small_rngs;
+
+use small_rngs::registry;
+use small_rngs::testu01::{self, Battery};
+use std::env;
+use std::process;
+
+fn print_usage(cmd: &str, names: Vec<String>) {
+    println!("Usage: {} BATTERY RNG
+where BATTERY is one of: smallcrush, crush, bigcrush
+and RNG is one of: {:?}
+
+Runs a TestU01 battery on the 32-bit output of an RNG seeded from the
+operating system, and prints a summary of the tests with suspicious p-values.",
+        cmd, names);
+}
+
+fn main() {
+    let names: Vec<String> = registry::GENERATORS.iter()
+                                 .map(|entry| String::from(entry.name))
+                                 .collect();
+
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        print_usage(&args[0], names);
+        process::exit(1);
+    }
+
+    let battery = match args[1].to_lowercase().as_str() {
+        "smallcrush" => Battery::SmallCrush,
+        "crush" => Battery::Crush,
+        "bigcrush" => Battery::BigCrush,
+        _ => {
+            println!("Error: unknown battery: {}", args[1]);
+            println!();
+            print_usage(&args[0], names);
+            process::exit(1);
+        }
+    };
+    let entry = match registry::find(&args[2]) {
+        Some(entry) => entry,
+        None => {
+            println!("Error: unknown RNG: {}", args[2]);
+            println!();
+            print_usage(&args[0], names);
+            process::exit(1);
+        }
+    };
+
+    let summary = testu01::run_battery(entry.from_entropy(), entry.name,
+                                       battery);
+
+    let suspicious = summary.suspicious();
+    let failures = summary.failures();
+    println!("{:?} on {}: {} tests, {} suspicious, {} failed",
+             battery, entry.name, summary.results.len(),
+             suspicious.len(), failures.len());
+    for &(ref name, p) in suspicious {
+        println!("  {:<30} p = {:.3e}", name, p);
+    }
+    if !failures.is_empty() {
+        process::exit(2);
+    }
+}
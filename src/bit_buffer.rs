@@ -0,0 +1,159 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Cheap booleans and small integers from a buffer of random bits.
+
+use rand_core::{RngCore, SeedableRng, Error};
+
+use crate::next_u128::NextU128;
+
+/// A wrapper that hands out the bits of each 64-bit output word one group at a
+/// time, instead of using a whole word for a boolean or a small integer.
+///
+/// Bits are taken from the least significant end of each word of `next_u64`.
+/// They form one stream: if a request needs more bits than are left in the
+/// buffer, the remaining bits become the low bits of the result, and the high
+/// bits come from the low end of the next word.
+///
+/// The `RngCore` methods pass through to the wrapped generator, and leave the
+/// buffer alone.
+#[derive(Clone)]
+pub struct BitBufferRng<R> {
+    rng: R,
+    buffer: u64,
+    bits_left: u32,
+}
+
+#[inline]
+fn low_mask(n: u32) -> u64 {
+    if n >= 64 { !0 } else { (1 << n) - 1 }
+}
+
+impl<R: RngCore> BitBufferRng<R> {
+    /// Wrap a generator, with an empty buffer.
+    pub fn new(rng: R) -> Self {
+        Self { rng, buffer: 0, bits_left: 0 }
+    }
+
+    /// Return one random bit as a boolean.
+    #[inline]
+    pub fn next_bool(&mut self) -> bool {
+        self.next_bits(1) != 0
+    }
+
+    /// Return `n` random bits in the low bits of the result, for `n` up to 64.
+    #[inline]
+    pub fn next_bits(&mut self, n: u32) -> u64 {
+        assert!(n <= 64, "can not return more than 64 bits");
+        if n <= self.bits_left {
+            let x = self.buffer & low_mask(n);
+            self.buffer = self.buffer.checked_shr(n).unwrap_or(0);
+            self.bits_left -= n;
+            x
+        } else {
+            // Here `bits_left < n <= 64`, so the shift does not overflow.
+            let low_bits = self.bits_left;
+            let high_bits = n - low_bits;
+            let word = self.rng.next_u64();
+            let x = self.buffer | ((word & low_mask(high_bits)) << low_bits);
+            self.buffer = word.checked_shr(high_bits).unwrap_or(0);
+            self.bits_left = 64 - high_bits;
+            x
+        }
+    }
+
+    /// Return the wrapped generator. Buffered bits are lost.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+}
+
+impl<R: SeedableRng + RngCore> SeedableRng for BitBufferRng<R> {
+    type Seed = R::Seed;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(R::from_seed(seed))
+    }
+
+    fn from_rng<S: RngCore>(rng: S) -> Result<Self, Error> {
+        R::from_rng(rng).map(Self::new)
+    }
+}
+
+impl<R: RngCore> RngCore for BitBufferRng<R> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+impl<R: NextU128> NextU128 for BitBufferRng<R> {
+    #[inline]
+    fn next_u128(&mut self) -> u128 {
+        self.rng.next_u128()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::RngCore;
+    use crate::mock::Words;
+    use super::*;
+
+    #[test]
+    fn least_significant_first() {
+        let mut rng = BitBufferRng::new(Words(&[0b1001_1101_0110]));
+        let mut bits = [false; 8];
+        for bit in bits.iter_mut() {
+            *bit = rng.next_bool();
+        }
+        assert_eq!(bits, [false, true, true, false, true, false, true, true]);
+        assert_eq!(rng.next_bits(4), 0b1001);
+    }
+
+    #[test]
+    fn spans_two_words() {
+        let mut rng = BitBufferRng::new(
+            Words(&[0xfedc_ba98_7654_3210, 0x0123_4567_89ab_cdef]));
+        assert_eq!(rng.next_bits(3), 0b000);
+        // 61 bits left of the first word, 3 bits from the second.
+        assert_eq!(rng.next_bits(64),
+                   0xfedc_ba98_7654_3210 >> 3 | (0b111 << 61));
+        assert_eq!(rng.next_bits(61), 0x0123_4567_89ab_cdef >> 3);
+    }
+
+    #[test]
+    fn zero_and_all_bits() {
+        let mut rng = BitBufferRng::new(
+            Words(&[0xfedc_ba98_7654_3210, 0x0123_4567_89ab_cdef]));
+        assert_eq!(rng.next_bits(0), 0);
+        assert_eq!(rng.next_bits(64), 0xfedc_ba98_7654_3210);
+        assert_eq!(rng.next_bits(0), 0);
+        assert_eq!(rng.next_bits(64), 0x0123_4567_89ab_cdef);
+    }
+
+    #[test]
+    #[should_panic(expected = "can not return more than 64 bits")]
+    fn more_than_64_bits() {
+        BitBufferRng::new(Words(&[])).next_bits(65);
+    }
+}
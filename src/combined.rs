@@ -0,0 +1,161 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Combinations of two generators.
+
+use rand_core::{RngCore, SeedableRng, Error, impls};
+use core::marker::PhantomData;
+use core::mem::size_of;
+
+/// A way to combine the outputs of two generators.
+pub trait Combiner {
+    fn combine_u32(a: u32, b: u32) -> u32;
+    fn combine_u64(a: u64, b: u64) -> u64;
+}
+
+/// Combine outputs with xor.
+#[derive(Clone, Debug)]
+pub struct CombineXor;
+
+impl Combiner for CombineXor {
+    #[inline]
+    fn combine_u32(a: u32, b: u32) -> u32 { a ^ b }
+    #[inline]
+    fn combine_u64(a: u64, b: u64) -> u64 { a ^ b }
+}
+
+/// Combine outputs with wrapping addition.
+#[derive(Clone, Debug)]
+pub struct CombineAdd;
+
+impl Combiner for CombineAdd {
+    #[inline]
+    fn combine_u32(a: u32, b: u32) -> u32 { a.wrapping_add(b) }
+    #[inline]
+    fn combine_u64(a: u64, b: u64) -> u64 { a.wrapping_add(b) }
+}
+
+/// Two generators running side by side, with their outputs combined.
+///
+/// Combining generators with different structure, like KISS does, can hide the
+/// weaknesses of either. For example adding a Weyl sequence to a linear
+/// generator breaks its linearity. The period is the least common multiple of
+/// the periods of both generators.
+///
+/// The seed is the seed of `A` followed by the seed of `B`.
+#[derive(Clone)]
+pub struct CombinedRng<A, B, C = CombineXor> {
+    a: A,
+    b: B,
+    combiner: PhantomData<C>,
+}
+
+impl<A, B, C> CombinedRng<A, B, C> {
+    /// Combine two generators.
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b, combiner: PhantomData }
+    }
+}
+
+// Enough for the seeds of any two generators in this crate; the largest is the
+// 40-byte seed of `Lfsr258Rng`.
+const MAX_SEED_LEN: usize = 80;
+
+/// The seed of a `CombinedRng`: the seed of `A` followed by the seed of `B`.
+pub struct CombinedSeed<SA, SB> {
+    bytes: [u8; MAX_SEED_LEN],
+    len_a: usize,
+    len_b: usize,
+    seeds: PhantomData<(SA, SB)>,
+}
+
+impl<SA, SB> CombinedSeed<SA, SB> {
+    // The seeds store their bytes inline, so their size is an upper bound on
+    // their length. Checked at compile time by referring to it.
+    const FITS: () = assert!(size_of::<SA>() + size_of::<SB>() <= MAX_SEED_LEN,
+                             "combined seed too large");
+}
+
+impl<SA, SB> Default for CombinedSeed<SA, SB>
+    where SA: Default + AsMut<[u8]>, SB: Default + AsMut<[u8]>
+{
+    fn default() -> Self {
+        let () = Self::FITS;
+        let len_a = SA::default().as_mut().len();
+        let len_b = SB::default().as_mut().len();
+        Self { bytes: [0; MAX_SEED_LEN], len_a, len_b, seeds: PhantomData }
+    }
+}
+
+impl<SA, SB> AsMut<[u8]> for CombinedSeed<SA, SB> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes[..self.len_a + self.len_b]
+    }
+}
+
+impl<A, B, C> SeedableRng for CombinedRng<A, B, C>
+    where A: SeedableRng, B: SeedableRng
+{
+    type Seed = CombinedSeed<A::Seed, B::Seed>;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let (bytes_a, bytes_b) =
+            seed.bytes[..seed.len_a + seed.len_b].split_at(seed.len_a);
+        let mut seed_a = A::Seed::default();
+        let mut seed_b = B::Seed::default();
+        seed_a.as_mut().copy_from_slice(bytes_a);
+        seed_b.as_mut().copy_from_slice(bytes_b);
+        Self::new(A::from_seed(seed_a), B::from_seed(seed_b))
+    }
+
+    fn from_rng<R: RngCore>(mut rng: R) -> Result<Self, Error> {
+        // Use the custom `from_rng` of the generators, if any.
+        let a = A::from_rng(&mut rng)?;
+        let b = B::from_rng(&mut rng)?;
+        Ok(Self::new(a, b))
+    }
+}
+
+impl<A, B, C> RngCore for CombinedRng<A, B, C>
+    where A: RngCore, B: RngCore, C: Combiner
+{
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        C::combine_u32(self.a.next_u32(), self.b.next_u32())
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        C::combine_u64(self.a.next_u64(), self.b.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "lfsr"))]
+mod tests {
+    use rand_core::{RngCore, SeedableRng};
+    use super::*;
+    use crate::Lfsr258Rng;
+
+    #[test]
+    fn largest_seeds_fit() {
+        let mut seed = <CombinedRng<Lfsr258Rng, Lfsr258Rng> as SeedableRng>::Seed::default();
+        assert_eq!(seed.as_mut().len(), 80);
+        let mut rng = CombinedRng::<Lfsr258Rng, Lfsr258Rng>::seed_from_u64(1);
+        rng.next_u64();
+    }
+}
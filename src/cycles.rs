@@ -0,0 +1,209 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Cycle analysis of generators with a small state.
+//!
+//! The cycle structure of chaotic generators like JSF, SFC and gjrand is not
+//! known. The standard technique is to study versions with 8- or 16-bit words,
+//! where the state space is small enough to walk through.
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::vec;
+
+/// A generator with a state of at most 64 bits, that can be stepped through
+/// without producing output.
+pub trait CycleState {
+    /// Number of bits in the state.
+    const STATE_BITS: u32;
+
+    /// The current state, packed into the low `STATE_BITS` bits.
+    fn state(&self) -> u64;
+
+    /// Construct a generator from a packed state. Bits above `STATE_BITS` are
+    /// ignored.
+    fn from_state(state: u64) -> Self;
+
+    /// Advance the state by one step.
+    fn step(&mut self);
+}
+
+/// The number of cycles of each length.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct CycleCounts {
+    /// Number of bits in the state.
+    pub state_bits: u32,
+    /// Maps a cycle length to the number of cycles with that length.
+    pub cycles: BTreeMap<u64, u64>,
+}
+
+#[cfg(feature = "std")]
+impl CycleCounts {
+    /// Total number of cycles.
+    pub fn total_cycles(&self) -> u64 {
+        self.cycles.values().sum()
+    }
+
+    /// Length of the shortest cycle.
+    pub fn min_length(&self) -> Option<u64> {
+        self.cycles.keys().next().cloned()
+    }
+
+    /// Length of the longest cycle.
+    pub fn max_length(&self) -> Option<u64> {
+        self.cycles.keys().next_back().cloned()
+    }
+
+    /// Number of states on cycles shorter than `limit`.
+    pub fn states_below(&self, limit: u64) -> u64 {
+        self.cycles.range(..limit).map(|(len, count)| len * count).sum()
+    }
+
+    /// Fraction of the state space on cycles shorter than `limit`.
+    pub fn fraction_below(&self, limit: u64) -> f64 {
+        self.states_below(limit) as f64 / (self.state_bits as f64).exp2()
+    }
+}
+
+/// Find all cycles by walking through every state, marking visited states in
+/// a bitset.
+///
+/// Needs 2<sup>`STATE_BITS`</sup> bits of memory, so the state can be at most
+/// 32 bits. Returns `None` if the transition function is not a permutation;
+/// then not every state is on a cycle.
+#[cfg(feature = "std")]
+pub fn exhaustive<R: CycleState>() -> Option<CycleCounts> {
+    assert!(R::STATE_BITS <= 32, "state too large for exhaustive search");
+    let n_states = 1u64 << R::STATE_BITS;
+    let mut visited = vec![0u64; n_states.div_ceil(64) as usize];
+    let mut cycles = BTreeMap::new();
+
+    for start in 0..n_states {
+        if visited[(start / 64) as usize] & (1 << (start % 64)) != 0 {
+            continue;
+        }
+        // With a permutation every state is on exactly one cycle, and cycles
+        // are disjoint. So a walk from an unvisited state only meets unvisited
+        // states until it returns to the start.
+        let mut rng = R::from_state(start);
+        let mut len = 0;
+        loop {
+            let s = rng.state();
+            let (word, bit) = ((s / 64) as usize, s % 64);
+            if visited[word] & (1 << bit) != 0 {
+                if s != start {
+                    return None;
+                }
+                break;
+            }
+            visited[word] |= 1 << bit;
+            len += 1;
+            rng.step();
+        }
+        *cycles.entry(len).or_insert(0) += 1;
+    }
+    Some(CycleCounts { state_bits: R::STATE_BITS, cycles })
+}
+
+/// Find the length of the cycle that `start` eventually ends up in, using
+/// Brent's algorithm.
+///
+/// Returns `None` if no cycle is found within `max_steps` steps.
+pub fn brent<R: CycleState>(start: u64, max_steps: u64) -> Option<u64> {
+    let mut tortoise = R::from_state(start).state();
+    let mut hare = R::from_state(start);
+    hare.step();
+
+    let mut power = 1;
+    let mut len = 1;
+    let mut steps = 1;
+    while hare.state() != tortoise {
+        if steps >= max_steps {
+            return None;
+        }
+        if power == len {
+            tortoise = hare.state();
+            power *= 2;
+            len = 0;
+        }
+        hare.step();
+        len += 1;
+        steps += 1;
+    }
+    Some(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A permutation of 16 states, with cycles (0), (1 2), (3 4 5),
+    // (6 7 8 9) and (10 11 12 13 14 15).
+    const PERMUTATION: [u8; 16] = [0, 2, 1, 4, 5, 3, 7, 8, 9, 6,
+                                   11, 12, 13, 14, 15, 10];
+
+    struct Permutation(u8);
+
+    impl CycleState for Permutation {
+        const STATE_BITS: u32 = 4;
+        fn state(&self) -> u64 { u64::from(self.0) }
+        fn from_state(state: u64) -> Self { Permutation(state as u8 & 0xf) }
+        fn step(&mut self) { self.0 = PERMUTATION[self.0 as usize]; }
+    }
+
+    // Not a permutation: every state ends up at 0.
+    struct Halve(u8);
+
+    impl CycleState for Halve {
+        const STATE_BITS: u32 = 4;
+        fn state(&self) -> u64 { u64::from(self.0) }
+        fn from_state(state: u64) -> Self { Halve(state as u8 & 0xf) }
+        fn step(&mut self) { self.0 /= 2; }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn exhaustive_permutation() {
+        let counts = exhaustive::<Permutation>().unwrap();
+        let cycles: std::vec::Vec<_> =
+            counts.cycles.iter().map(|(&len, &n)| (len, n)).collect();
+        assert_eq!(cycles, [(1, 1), (2, 1), (3, 1), (4, 1), (6, 1)]);
+        assert_eq!(counts.total_cycles(), 5);
+        assert_eq!(counts.min_length(), Some(1));
+        assert_eq!(counts.max_length(), Some(6));
+        assert_eq!(counts.states_below(4), 1 + 2 + 3);
+        assert_eq!(counts.fraction_below(4), 6.0 / 16.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn exhaustive_not_a_permutation() {
+        assert!(exhaustive::<Halve>().is_none());
+    }
+
+    #[test]
+    fn brent_cycle_lengths() {
+        let expected = [1, 2, 2, 3, 3, 3, 4, 4, 4, 4, 6, 6, 6, 6, 6, 6];
+        for (start, &len) in expected.iter().enumerate() {
+            assert_eq!(brent::<Permutation>(start as u64, 100), Some(len));
+        }
+        // The cycle of `Halve` is the fixed point 0, after a tail.
+        assert_eq!(brent::<Halve>(12, 100), Some(1));
+    }
+
+    #[test]
+    fn brent_gives_up() {
+        // The tortoise moves after 1, 3 and 7 steps, and the hare is back at
+        // it after 13.
+        assert_eq!(brent::<Permutation>(10, 12), None);
+        assert_eq!(brent::<Permutation>(10, 13), Some(6));
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Seeding from a source of entropy.
+//!
+//! With the `getrandom` feature (enabled by default) entropy comes from the
+//! operating system, through the getrandom crate. On wasm32-unknown-unknown
+//! enable the `js` feature to use the `crypto` API of the browser or Node.js,
+//! or the `custom` feature to use a backend registered with
+//! `getrandom::register_custom_getrandom!`.
+//!
+//! On targets without getrandom support, like bare-metal, register a function
+//! with [`seed_from_hardware`] instead, for example one that reads a hardware
+//! random number generator. It is also used as a fallback if getrandom fails.
+
+use core::mem;
+#[cfg(not(feature = "getrandom"))]
+use core::num::NonZeroU32;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use rand_core::{RngCore, SeedableRng, Error, impls};
+
+/// A function that fills a buffer with entropy.
+pub type HardwareSeedFn = fn(&mut [u8]) -> Result<(), Error>;
+
+// A `HardwareSeedFn` stored as `usize`, or 0 if none is registered.
+static HARDWARE_SEED: AtomicUsize = AtomicUsize::new(0);
+
+/// Error code returned when no source of entropy is available.
+pub const NO_ENTROPY_SOURCE: u32 = Error::CUSTOM_START;
+
+/// Register a function to fill a buffer with entropy, for targets where
+/// getrandom is not available or not enabled.
+///
+/// Replaces any function registered before.
+pub fn seed_from_hardware(f: HardwareSeedFn) {
+    HARDWARE_SEED.store(f as usize, Ordering::Release);
+}
+
+fn hardware_seed() -> Option<HardwareSeedFn> {
+    match HARDWARE_SEED.load(Ordering::Acquire) {
+        0 => None,
+        // Safe because only `seed_from_hardware` stores a non-zero value,
+        // which is a `HardwareSeedFn`.
+        f => Some(unsafe { mem::transmute::<usize, HardwareSeedFn>(f) }),
+    }
+}
+
+#[cfg(feature = "getrandom")]
+fn os_seed(dest: &mut [u8]) -> Result<(), Error> {
+    getrandom::getrandom(dest).map_err(|e| Error::from(e.code()))
+}
+
+#[cfg(not(feature = "getrandom"))]
+fn os_seed(_dest: &mut [u8]) -> Result<(), Error> {
+    Err(Error::from(NonZeroU32::new(NO_ENTROPY_SOURCE).unwrap()))
+}
+
+/// Fill `dest` with entropy.
+///
+/// Uses getrandom if the feature is enabled, and otherwise or if that fails
+/// the function registered with [`seed_from_hardware`]. If both are
+/// unavailable the error of getrandom is returned, or an error with code
+/// [`NO_ENTROPY_SOURCE`].
+pub fn fill_entropy(dest: &mut [u8]) -> Result<(), Error> {
+    match (os_seed(dest), hardware_seed()) {
+        (Ok(()), _) => Ok(()),
+        (Err(_), Some(f)) => f(dest),
+        (Err(e), None) => Err(e),
+    }
+}
+
+/// Create a generator seeded with entropy from [`fill_entropy`].
+pub fn try_from_entropy<R: SeedableRng>() -> Result<R, Error> {
+    let mut seed = R::Seed::default();
+    fill_entropy(seed.as_mut())?;
+    Ok(R::from_seed(seed))
+}
+
+/// Create a generator seeded with entropy from [`fill_entropy`].
+///
+/// This replaces `SeedableRng::from_entropy`, which always uses the
+/// operating system.
+///
+/// # Panics
+///
+/// If no source of entropy is available.
+pub fn from_entropy<R: SeedableRng>() -> R {
+    match try_from_entropy() {
+        Ok(rng) => rng,
+        Err(err) => panic!("from_entropy failed: {}", err),
+    }
+}
+
+/// A generator that returns entropy from [`fill_entropy`], for example to
+/// reseed other generators.
+///
+/// The infallible methods panic if no source of entropy is available.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EntropySource;
+
+impl RngCore for EntropySource {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(err) = fill_entropy(dest) {
+            panic!("fill_entropy failed: {}", err);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        fill_entropy(dest)
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A default generator for the target platform.
+
+use rand_core::{RngCore, SeedableRng, Error};
+
+use crate::entropy;
+use crate::next_u128::NextU128;
+
+#[cfg(target_pointer_width = "64")]
+type Inner = crate::Sfc64Rng;
+#[cfg(not(target_pointer_width = "64"))]
+type Inner = crate::Sfc32Rng;
+
+/// A fast generator that passes BigCrush and PractRand, for when you do not
+/// want to pick one yourself.
+///
+/// This is `Sfc64Rng` on targets with 64-bit pointers, and `Sfc32Rng` on
+/// others, like 32-bit targets and WebAssembly. So the output for the same
+/// seed differs between platforms, and may change in future versions. Use one
+/// of the generators directly if you need reproducible output.
+#[derive(Clone)]
+pub struct FastRng(Inner);
+
+impl FastRng {
+    /// Create the generator, seeded with entropy from
+    /// [`entropy::fill_entropy`](entropy/fn.fill_entropy.html).
+    ///
+    /// Panics if no source of entropy is available.
+    pub fn new() -> Self {
+        FastRng(entropy::from_entropy())
+    }
+}
+
+impl Default for FastRng {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SeedableRng for FastRng {
+    type Seed = <Inner as SeedableRng>::Seed;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        FastRng(Inner::from_seed(seed))
+    }
+
+    fn from_rng<R: RngCore>(rng: R) -> Result<Self, Error> {
+        Inner::from_rng(rng).map(FastRng)
+    }
+}
+
+impl RngCore for FastRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl NextU128 for FastRng {
+    #[inline]
+    fn next_u128(&mut self) -> u128 {
+        self.0.next_u128()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::{RngCore, SeedableRng};
+    use super::*;
+
+    // The output of `Sfc64Rng` for the same seed.
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn fixed_seed() {
+        let mut seed = [0u8; 24];
+        for (i, x) in seed.iter_mut().enumerate() { *x = i as u8 + 1; }
+        let mut rng = FastRng::from_seed(seed);
+        assert_eq!(rng.next_u64(), 0x4663_6c08_6277_084a);
+        assert_eq!(rng.next_u64(), 0xb8b2_66be_17c1_19c5);
+        assert_eq!(rng.next_u64(), 0x425a_e870_91ed_1dda);
+        assert_eq!(rng.next_u64(), 0x7c28_83ef_c3d3_0571);
+    }
+
+    // The output of `Sfc32Rng` for the same seed.
+    #[cfg(not(target_pointer_width = "64"))]
+    #[test]
+    fn fixed_seed() {
+        let mut seed = [0u8; 12];
+        for (i, x) in seed.iter_mut().enumerate() { *x = i as u8 + 1; }
+        let mut rng = FastRng::from_seed(seed);
+        assert_eq!(rng.next_u64(), 0xf7f8_742b_59d2_7312);
+        assert_eq!(rng.next_u64(), 0xccee_795e_e8b3_6be8);
+        assert_eq!(rng.next_u64(), 0x7472_ae23_e4f9_64c1);
+        assert_eq!(rng.next_u64(), 0x3f89_00db_c270_782a);
+    }
+}
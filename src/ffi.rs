@@ -0,0 +1,120 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! C interface to all generators in the registry.
+//!
+//! The header `include/small_rngs.h` is generated from this module with
+//! `cbindgen --config cbindgen.toml --output include/small_rngs.h`.
+
+use rand_core::RngCore;
+use std::boxed::Box;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::panic;
+use std::ptr;
+use std::slice;
+
+use crate::registry;
+
+/// An opaque handle to a generator.
+pub struct RngHandle {
+    rng: Box<dyn RngCore>,
+}
+
+unsafe fn find_entry(name: *const c_char) -> Option<&'static registry::RngEntry> {
+    if name.is_null() {
+        return None;
+    }
+    let name = CStr::from_ptr(name).to_str().ok()?;
+    registry::find(name)
+}
+
+/// Create the generator with the given name.
+///
+/// The seed must be exactly as long as returned by `small_rngs_seed_len`. If
+/// `seed_ptr` is null the generator is seeded with entropy, from the operating
+/// system or else from the function registered with
+/// `entropy::seed_from_hardware`.
+///
+/// Returns null if the name is unknown, the seed has the wrong length, the
+/// generator rejects the seed, or no source of entropy is available.
+///
+/// # Safety
+///
+/// `name` must be null or a nul-terminated string, and `seed_ptr` must be
+/// null or point to `seed_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn small_rngs_new(name: *const c_char,
+                                        seed_ptr: *const u8,
+                                        seed_len: usize) -> *mut RngHandle {
+    let entry = match find_entry(name) {
+        Some(entry) => entry,
+        None => return ptr::null_mut(),
+    };
+    let result = if seed_ptr.is_null() {
+        panic::catch_unwind(|| Some(entry.from_entropy()))
+    } else {
+        let seed = slice::from_raw_parts(seed_ptr, seed_len);
+        panic::catch_unwind(|| entry.from_seed(seed))
+    };
+    match result {
+        Ok(Some(rng)) => Box::into_raw(Box::new(RngHandle { rng })),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// The seed length in bytes of the generator with the given name, or 0 if
+/// the name is unknown.
+///
+/// # Safety
+///
+/// `name` must be null or a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn small_rngs_seed_len(name: *const c_char) -> usize {
+    find_entry(name).map_or(0, |entry| entry.seed_len())
+}
+
+/// Return the next 64 bits of output.
+///
+/// # Safety
+///
+/// `rng` must be a valid handle returned by `small_rngs_new`.
+#[no_mangle]
+pub unsafe extern "C" fn small_rngs_next_u64(rng: *mut RngHandle) -> u64 {
+    (*rng).rng.next_u64()
+}
+
+/// Fill `len` bytes at `buf` with output.
+///
+/// # Safety
+///
+/// `rng` must be a valid handle returned by `small_rngs_new`, and `buf` must
+/// point to `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn small_rngs_fill(rng: *mut RngHandle,
+                                         buf: *mut u8,
+                                         len: usize) {
+    if len == 0 {
+        return;
+    }
+    (*rng).rng.fill_bytes(slice::from_raw_parts_mut(buf, len))
+}
+
+/// Free a generator. Does nothing if `rng` is null.
+///
+/// # Safety
+///
+/// `rng` must be null or a handle returned by `small_rngs_new` that is not
+/// yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn small_rngs_free(rng: *mut RngHandle) {
+    if !rng.is_null() {
+        drop(Box::from_raw(rng));
+    }
+}
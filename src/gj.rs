@@ -10,6 +10,7 @@
 //! Geronimo Jones' random number generator.
 
 use rand_core::{RngCore, SeedableRng, Error, impls, le};
+use crate::cycles::CycleState;
 
 /// A small random number generator by Geronimo Jones.
 ///
@@ -83,3 +84,136 @@ impl RngCore for GjRng {
         Ok(self.fill_bytes(dest))
     }
 }
+
+
+
+macro_rules! gj_small {
+    ($(#[$doc:meta])* $name:ident, $ty:ident, $bits:expr, $c_init:expr,
+     $rot1:expr, $rot2:expr, $rot3:expr, $weyl:expr) => {
+        $(#[$doc])*
+        #[derive(Clone)]
+        pub struct $name {
+            a: $ty,
+            b: $ty,
+            c: $ty,
+            d: $ty,
+        }
+
+        impl $name {
+            #[inline]
+            fn next_word(&mut self) -> $ty {
+                self.b = self.b.wrapping_add(self.c);
+                self.a = self.a.rotate_left($rot1);
+                self.c ^= self.b;
+
+                self.d = self.d.wrapping_add($weyl);
+
+                self.a = self.a.wrapping_add(self.b);
+                self.c = self.c.rotate_left($rot2);
+                self.b ^= self.a;
+
+                self.a = self.a.wrapping_add(self.c);
+                self.b = self.b.rotate_left($rot3);
+                self.c = self.c.wrapping_add(self.a);
+                self.b = self.b.wrapping_add(self.d);
+
+                self.a
+            }
+        }
+
+        impl SeedableRng for $name {
+            type Seed = [u8; 2 * $bits / 8];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                let mut seed_words = [0; 2];
+                for (word, bytes) in seed_words.iter_mut()
+                                               .zip(seed.chunks($bits / 8)) {
+                    let mut buf = [0u8; $bits / 8];
+                    buf.copy_from_slice(bytes);
+                    *word = $ty::from_le_bytes(buf);
+                }
+                let mut state = Self { a: seed_words[0],
+                                       b: seed_words[1],
+                                       c: $c_init,
+                                       d: 0};
+                for _ in 0..14 {
+                    state.next_word();
+                }
+                state
+            }
+        }
+
+        impl RngCore for $name {
+            #[inline]
+            fn next_u32(&mut self) -> u32 {
+                let mut x = 0;
+                for i in 0..(32 / $bits) {
+                    x |= (self.next_word() as u32) << (i * $bits);
+                }
+                x
+            }
+
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                impls::next_u64_via_u32(self)
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                impls::fill_bytes_via_next(self, dest)
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        impl CycleState for $name {
+            const STATE_BITS: u32 = 4 * $bits;
+
+            fn state(&self) -> u64 {
+                self.a as u64 | (self.b as u64) << $bits |
+                (self.c as u64) << (2 * $bits) | (self.d as u64) << (3 * $bits)
+            }
+
+            fn from_state(state: u64) -> Self {
+                Self { a: state as $ty,
+                       b: (state >> $bits) as $ty,
+                       c: (state >> (2 * $bits)) as $ty,
+                       d: (state >> (3 * $bits)) as $ty }
+            }
+
+            fn step(&mut self) {
+                self.next_word();
+            }
+        }
+    }
+}
+
+gj_small!(
+    /// Geronimo Jones' generator scaled down to 8-bit words, to study the cycle
+    /// structure of gjrand.
+    ///
+    /// - Author: Geronimo Jones, scaled down by Chris Doty-Humphrey
+    /// - Source: [PractRand](http://pracrand.sourceforge.net/)
+    /// - License: GPL v2 or v3
+    /// - Period: min >= 2<sup>8</sup>, see `cycle_analysis`
+    /// - State: 32 bits
+    /// - Word size: 8 bits
+    /// - Seed size: 16 bits
+    /// - Low quality, for analysis only
+    Gj8Rng, u8, 8, 201, 4, 2, 5, 0x35);
+
+gj_small!(
+    /// Geronimo Jones' generator scaled down to 16-bit words, to study the
+    /// cycle structure of gjrand.
+    ///
+    /// - Author: Geronimo Jones, scaled down by Chris Doty-Humphrey
+    /// - Source: [PractRand](http://pracrand.sourceforge.net/)
+    /// - License: GPL v2 or v3
+    /// - Period: min >= 2<sup>16</sup>, see `cycle_analysis`
+    /// - State: 64 bits
+    /// - Word size: 16 bits
+    /// - Seed size: 32 bits
+    /// - Low quality, for analysis only
+    Gj16Rng, u16, 16, 5001, 8, 5, 10, 0x96a5);
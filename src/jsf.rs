@@ -10,6 +10,7 @@
 //! Bob Jenkins small fast pseudorandom number generator.
 
 use rand_core::{RngCore, SeedableRng, Error, impls, le};
+use crate::cycles::CycleState;
 
 /// A small random number generator designed by Bob Jenkins.
 ///
@@ -135,3 +136,117 @@ impl RngCore for Jsf64Rng {
         Ok(self.fill_bytes(dest))
     }
 }
+
+
+
+macro_rules! jsf_small {
+    ($(#[$doc:meta])* $name:ident, $ty:ident, $bits:expr, $fleaseed:expr,
+     $rot1:expr, $rot2:expr) => {
+        $(#[$doc])*
+        #[derive(Clone)]
+        pub struct $name {
+            a: $ty,
+            b: $ty,
+            c: $ty,
+            d: $ty,
+        }
+
+        impl $name {
+            #[inline]
+            fn next_word(&mut self) -> $ty {
+                let e = self.a.wrapping_sub(self.b.rotate_left($rot1));
+                self.a = self.b ^ self.c.rotate_left($rot2);
+                self.b = self.c.wrapping_add(self.d);
+                self.c = self.d.wrapping_add(e);
+                self.d = e.wrapping_add(self.a);
+                self.d
+            }
+        }
+
+        impl SeedableRng for $name {
+            type Seed = [u8; $bits / 8];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                let seed = $ty::from_le_bytes(seed);
+                let mut state = Self { a: $fleaseed, b: seed, c: seed, d: seed };
+                for _ in 0..20 {
+                    state.next_word();
+                }
+                state
+            }
+        }
+
+        impl RngCore for $name {
+            #[inline]
+            fn next_u32(&mut self) -> u32 {
+                let mut x = 0;
+                for i in 0..(32 / $bits) {
+                    x |= (self.next_word() as u32) << (i * $bits);
+                }
+                x
+            }
+
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                impls::next_u64_via_u32(self)
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                impls::fill_bytes_via_next(self, dest)
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        impl CycleState for $name {
+            const STATE_BITS: u32 = 4 * $bits;
+
+            fn state(&self) -> u64 {
+                self.a as u64 | (self.b as u64) << $bits |
+                (self.c as u64) << (2 * $bits) | (self.d as u64) << (3 * $bits)
+            }
+
+            fn from_state(state: u64) -> Self {
+                Self { a: state as $ty,
+                       b: (state >> $bits) as $ty,
+                       c: (state >> (2 * $bits)) as $ty,
+                       d: (state >> (3 * $bits)) as $ty }
+            }
+
+            fn step(&mut self) {
+                self.next_word();
+            }
+        }
+    }
+}
+
+jsf_small!(
+    /// Bob Jenkins' small fast generator scaled down to 8-bit words, to study
+    /// the cycle structure of JSF.
+    ///
+    /// - Author: Bob Jenkins, scaled down by Chris Doty-Humphrey
+    /// - License: Public domain
+    /// - Source: [PractRand](http://pracrand.sourceforge.net/)
+    /// - Period: unknown, see `cycle_analysis`
+    /// - State: 32 bits
+    /// - Word size: 8 bits
+    /// - Seed size: 8 bits
+    /// - Low quality, for analysis only
+    Jsf8Rng, u8, 8, 0xed, 1, 4);
+
+jsf_small!(
+    /// Bob Jenkins' small fast generator scaled down to 16-bit words, to study
+    /// the cycle structure of JSF.
+    ///
+    /// - Author: Bob Jenkins, scaled down by Chris Doty-Humphrey
+    /// - License: Public domain
+    /// - Source: [PractRand](http://pracrand.sourceforge.net/)
+    /// - Period: unknown, see `cycle_analysis`
+    /// - State: 64 bits
+    /// - Word size: 16 bits
+    /// - Seed size: 16 bits
+    /// - Low quality, for analysis only
+    Jsf16Rng, u16, 16, 0x5eed, 13, 8);
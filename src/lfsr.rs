@@ -0,0 +1,257 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Combined LFSR (Tausworthe) random number generators
+
+use rand_core::{RngCore, SeedableRng, Error, impls, le};
+
+/// The combined LFSR generator LFSR113, of four Tausworthe components.
+///
+/// Each component must start with one of its significant bits set, which
+/// means component `i` must be at least `Lfsr113Rng::MIN_SEED[i]`. A smaller
+/// value is increased by that minimum, like the GSL does for `taus113`.
+///
+/// - Author: Pierre L'Ecuyer
+/// - License: unknown
+/// - Source: ["Tables of Maximally-Equidistributed Combined LFSR
+///   Generators"](https://www.iro.umontreal.ca/~lecuyer/myftp/papers/tausme2.ps).
+/// - Period: ~2<sup>113</sup>
+/// - State: 128 bits
+/// - Word size: 32 bits
+/// - Seed size: 128 bits
+#[derive(Clone, Debug)]
+pub struct Lfsr113Rng {
+    z: [u32; 4],
+}
+
+impl Lfsr113Rng {
+    /// The smallest valid value of each component of the state.
+    pub const MIN_SEED: [u32; 4] = [2, 8, 16, 128];
+}
+
+impl SeedableRng for Lfsr113Rng {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut z = [0u32; 4];
+        le::read_u32_into(&seed, &mut z);
+        for (z, min) in z.iter_mut().zip(Self::MIN_SEED.iter()) {
+            if *z < *min { *z += *min; }
+        }
+        Self { z }
+    }
+
+    fn from_rng<R: RngCore>(mut rng: R) -> Result<Self, Error> {
+        let mut z = [0u32; 4];
+        for (z, min) in z.iter_mut().zip(Self::MIN_SEED.iter()) {
+            while *z < *min { *z = rng.next_u32() };
+        }
+        Ok(Self { z })
+    }
+}
+
+impl RngCore for Lfsr113Rng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let [z1, z2, z3, z4] = self.z;
+        let b = ((z1 << 6) ^ z1) >> 13;
+        let z1 = ((z1 & 0xffff_fffe) << 18) ^ b;
+        let b = ((z2 << 2) ^ z2) >> 27;
+        let z2 = ((z2 & 0xffff_fff8) << 2) ^ b;
+        let b = ((z3 << 13) ^ z3) >> 21;
+        let z3 = ((z3 & 0xffff_fff0) << 7) ^ b;
+        let b = ((z4 << 3) ^ z4) >> 12;
+        let z4 = ((z4 & 0xffff_ff80) << 13) ^ b;
+        self.z = [z1, z2, z3, z4];
+        z1 ^ z2 ^ z3 ^ z4
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_u32(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+
+/// The combined LFSR generator LFSR258, of five Tausworthe components.
+///
+/// Each component must start with one of its significant bits set, which
+/// means component `i` must be at least `Lfsr258Rng::MIN_SEED[i]`. A smaller
+/// value is increased by that minimum.
+///
+/// - Author: Pierre L'Ecuyer
+/// - License: unknown
+/// - Source: ["Tables of Maximally-Equidistributed Combined LFSR
+///   Generators"](https://www.iro.umontreal.ca/~lecuyer/myftp/papers/tausme2.ps).
+/// - Period: ~2<sup>258</sup>
+/// - State: 320 bits
+/// - Word size: 64 bits
+/// - Seed size: 320 bits
+#[derive(Clone, Debug)]
+pub struct Lfsr258Rng {
+    y: [u64; 5],
+}
+
+impl Lfsr258Rng {
+    /// The smallest valid value of each component of the state.
+    pub const MIN_SEED: [u64; 5] = [2, 512, 4096, 131072, 8388608];
+}
+
+/// The seed of `Lfsr258Rng`: 40 bytes, one little-endian `u64` per component.
+///
+/// A wrapper, because arrays of more than 32 bytes do not implement `Default`.
+#[derive(Clone)]
+pub struct Lfsr258Seed(pub [u8; 40]);
+
+impl Default for Lfsr258Seed {
+    fn default() -> Self {
+        Lfsr258Seed([0; 40])
+    }
+}
+
+impl AsMut<[u8]> for Lfsr258Seed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl SeedableRng for Lfsr258Rng {
+    type Seed = Lfsr258Seed;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut y = [0u64; 5];
+        le::read_u64_into(&seed.0, &mut y);
+        for (y, min) in y.iter_mut().zip(Self::MIN_SEED.iter()) {
+            if *y < *min { *y += *min; }
+        }
+        Self { y }
+    }
+
+    fn from_rng<R: RngCore>(mut rng: R) -> Result<Self, Error> {
+        let mut y = [0u64; 5];
+        for (y, min) in y.iter_mut().zip(Self::MIN_SEED.iter()) {
+            while *y < *min { *y = rng.next_u64() };
+        }
+        Ok(Self { y })
+    }
+}
+
+impl RngCore for Lfsr258Rng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let [y1, y2, y3, y4, y5] = self.y;
+        let b = ((y1 << 1) ^ y1) >> 53;
+        let y1 = ((y1 & 0xffff_ffff_ffff_fffe) << 10) ^ b;
+        let b = ((y2 << 24) ^ y2) >> 50;
+        let y2 = ((y2 & 0xffff_ffff_ffff_fe00) << 5) ^ b;
+        let b = ((y3 << 3) ^ y3) >> 23;
+        let y3 = ((y3 & 0xffff_ffff_ffff_f000) << 29) ^ b;
+        let b = ((y4 << 5) ^ y4) >> 24;
+        let y4 = ((y4 & 0xffff_ffff_fffe_0000) << 23) ^ b;
+        let b = ((y5 << 3) ^ y5) >> 33;
+        let y5 = ((y5 & 0xffff_ffff_ff80_0000) << 8) ^ b;
+        self.y = [y1, y2, y3, y4, y5];
+        y1 ^ y2 ^ y3 ^ y4 ^ y5
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::SeedableRng;
+    use crate::mock::Words;
+    use super::*;
+
+    fn lfsr113(z: [u32; 4]) -> Lfsr113Rng {
+        let mut seed = [0u8; 16];
+        for (bytes, z) in seed.chunks_mut(4).zip(z.iter()) {
+            bytes.copy_from_slice(&z.to_le_bytes());
+        }
+        Lfsr113Rng::from_seed(seed)
+    }
+
+    fn lfsr258(y: [u64; 5]) -> Lfsr258Rng {
+        let mut seed = Lfsr258Seed::default();
+        for (bytes, y) in seed.0.chunks_mut(8).zip(y.iter()) {
+            bytes.copy_from_slice(&y.to_le_bytes());
+        }
+        Lfsr258Rng::from_seed(seed)
+    }
+
+    // Outputs of `lfsr113.c` by L'Ecuyer, with its default seed.
+    #[test]
+    fn lfsr113_reference() {
+        let mut rng = lfsr113([987654321; 4]);
+        let expected = [3952563604, 1192989748, 2423800670, 1230242343,
+                        788132445];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u32(), x);
+        }
+        for _ in expected.len()..999 {
+            rng.next_u32();
+        }
+        assert_eq!(rng.next_u32(), 2080562873);
+    }
+
+    // Outputs of `lfsr258.c` by L'Ecuyer, with its default seed.
+    #[test]
+    fn lfsr258_reference() {
+        let mut rng = lfsr258([123456789123456789; 5]);
+        let expected = [9973624093427544505, 17203455483290184537,
+                        3469538395387468010, 8795315472740051422,
+                        6545042816095807101];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u64(), x);
+        }
+        for _ in expected.len()..999 {
+            rng.next_u64();
+        }
+        assert_eq!(rng.next_u64(), 11805730128939023603);
+    }
+
+    #[test]
+    fn from_seed_raises_small_components() {
+        assert_eq!(lfsr113([0, 1, 2, 3]).z, [2, 9, 18, 131]);
+        assert_eq!(lfsr113([2, 8, 16, 128]).z, [2, 8, 16, 128]);
+        assert_eq!(lfsr258([0, 511, 4096, 5, 8388608]).y,
+                   [2, 1023, 4096, 131077, 8388608]);
+    }
+
+    #[test]
+    fn from_rng_redraws_small_components() {
+        let rng = Words(&[0, 1, 7, 3, 8, 100, 15, 16, 127, 128]);
+        assert_eq!(Lfsr113Rng::from_rng(rng).unwrap().z, [7, 8, 100, 128]);
+        let rng = Words(&[1, 2, 511, 512, 4095, 1 << 40, 131071, 131072, 0,
+                           8388607, 8388608]);
+        assert_eq!(Lfsr258Rng::from_rng(rng).unwrap().y,
+                   [2, 512, 1 << 40, 131072, 8388608]);
+    }
+}
@@ -1,34 +1,119 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
 extern crate rand_core;
 extern crate core;
 
+mod bit_buffer;
+#[cfg(feature = "ci")]
 mod ciprng;
+mod combined;
+#[cfg(feature = "sfc")]
+mod fast_rng;
+#[cfg(feature = "gj")]
 mod gj;
+#[cfg(feature = "jsf")]
 mod jsf;
+#[cfg(feature = "kiss")]
 mod kiss;
+#[cfg(feature = "lfsr")]
+mod lfsr;
+#[cfg(test)]
+mod mock;
+#[cfg(feature = "msws")]
 mod msws;
+mod next_u128;
+#[cfg(feature = "pcg")]
 mod pcg;
+#[cfg(any(feature = "msws", feature = "pcg"))]
+mod rand_digits;
+mod reseeding;
+#[cfg(feature = "sapparot")]
 mod sapparoth;
+#[cfg(feature = "sfc")]
 mod sfc;
+#[cfg(feature = "simd")]
+mod simd;
+mod transform;
+#[cfg(feature = "velox")]
 mod velox;
+#[cfg(feature = "weyl")]
+mod weyl;
+#[cfg(all(feature = "xoroshiro", target_pointer_width = "32"))]
+mod wmul;
+#[cfg(feature = "xorshift")]
 mod xorshift;
+#[cfg(feature = "xorshift")]
 mod xorshift_plus;
+#[cfg(feature = "xorshift")]
 mod xorshift_mt;
+#[cfg(feature = "xoroshiro")]
 mod xoroshiro;
+#[cfg(feature = "xoroshiro")]
 mod xoroshiro_mt;
+#[cfg(feature = "xsm")]
 mod xsm;
 
+#[cfg(feature = "std")]
+pub mod avalanche;
+pub mod cycles;
+pub mod entropy;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "testu01")]
+pub mod testu01;
+
+pub use self::bit_buffer::BitBufferRng;
+#[cfg(feature = "ci")]
 pub use self::ciprng::CiRng;
-pub use self::gj::GjRng;
-pub use self::jsf::{Jsf32Rng, Jsf64Rng};
+pub use self::combined::{CombinedRng, CombinedSeed, Combiner, CombineXor, CombineAdd};
+#[cfg(feature = "sfc")]
+pub use self::fast_rng::FastRng;
+#[cfg(feature = "gj")]
+pub use self::gj::{GjRng, Gj8Rng, Gj16Rng};
+#[cfg(feature = "jsf")]
+pub use self::jsf::{Jsf32Rng, Jsf64Rng, Jsf8Rng, Jsf16Rng};
+#[cfg(feature = "kiss")]
 pub use self::kiss::{Kiss32Rng, Kiss64Rng};
+#[cfg(feature = "lfsr")]
+pub use self::lfsr::{Lfsr113Rng, Lfsr258Rng, Lfsr258Seed};
+#[cfg(feature = "msws")]
 pub use self::msws::MswsRng;
-pub use self::pcg::{PcgXsh64LcgRng, PcgXsl64LcgRng, PcgXsl128McgRng, MwpRng};
+pub use self::next_u128::NextU128;
+#[cfg(feature = "pcg")]
+pub use self::pcg::{Pcg, PcgState, PcgOutput, PcgWord,
+                     Lcg64, Mcg64, Lcg128, Mcg128,
+                     XshRr, XslRr, RxsMXs, Dxsm,
+                     PcgXsh64LcgRng, PcgXsl64LcgRng, PcgXsl128McgRng, MwpRng};
+pub use self::reseeding::{ReseedingSmallRng, DEFAULT_RESEED_BUDGET};
+#[cfg(feature = "sapparot")]
 pub use self::sapparoth::{Sapparot32Rng, Sapparot64Rng};
-pub use self::sfc::{Sfc32Rng, Sfc64Rng};
+#[cfg(feature = "sfc")]
+pub use self::sfc::{Sfc32Rng, Sfc64Rng, Sfc8Rng, Sfc16Rng};
+#[cfg(feature = "simd")]
+pub use self::simd::{Sfc64x4Rng, Sfc64x4Core, Xoshiro256x4Rng, Xoshiro256x4Core};
+pub use self::transform::{TransformRng, Transform};
+#[cfg(feature = "velox")]
 pub use self::velox::Velox3bRng;
-pub use self::xorshift::{Xorshift128_32Rng, Xorshift128_64Rng};
+#[cfg(feature = "weyl")]
+pub use self::weyl::WeylRng;
+#[cfg(feature = "xorshift")]
+pub use self::xorshift::{Xorshift32Engine, Xorshift64Engine,
+                          Xorshift128_32Engine, Xorshift128_64Engine,
+                          Xorshift32Rng, Xorshift64Rng,
+                          Xorshift128_32Rng, Xorshift128_64Rng};
+#[cfg(feature = "xorshift")]
 pub use self::xorshift_plus::Xorshift128PlusRng;
+#[cfg(feature = "xorshift")]
 pub use self::xorshift_mt::{XorshiftMt32Rng, XorshiftMt64Rng};
-pub use self::xoroshiro::{Xoroshiro128PlusRng, Xoroshiro64PlusRng};
+#[cfg(feature = "xoroshiro")]
+pub use self::xoroshiro::{Xoroshiro128PlusRng, Xoroshiro128PlusPlusRng,
+                           Xoroshiro128StarStarRng,
+                           Xoroshiro64PlusRng, Xoroshiro64StarStarRng};
+#[cfg(feature = "xoroshiro")]
 pub use self::xoroshiro_mt::{XoroshiroMt32of128Rng, XoroshiroMt64of128Rng};
+#[cfg(feature = "xsm")]
 pub use self::xsm::{Xsm32Rng, Xsm64Rng};
@@ -11,6 +11,8 @@
 
 use rand_core::{RngCore, SeedableRng, Error, impls, le};
 
+use crate::rand_digits::weyl_constant;
+
 /// Middle Square Weyl Sequence RNG
 ///
 /// - Author: Bernard Widynski
@@ -27,6 +29,24 @@ pub struct MswsRng {
     s: u64,
 }
 
+impl MswsRng {
+    /// Construct the generator for stream `index`, starting from `seed`.
+    ///
+    /// Instead of taking the Weyl constant from the seed, it is derived from
+    /// `index` like `init_rand_digits` of the reference code: an odd constant
+    /// where each 32-bit half has 8 different non-zero hexadecimal digits.
+    /// The same index always gives the same constant.
+    pub fn with_stream_index(seed: u64, index: u64) -> Self {
+        Self { x: seed, w: 0, s: weyl_constant(index) }
+    }
+
+    /// Whether `from_seed` accepts `seed`: the upper 32 bits of the Weyl
+    /// constant, the first word of the seed, must not all be zero.
+    pub fn is_valid_seed(seed: &[u8; 16]) -> bool {
+        seed[4..8].iter().any(|&b| b != 0)
+    }
+}
+
 impl SeedableRng for MswsRng {
     type Seed = [u8; 16];
 
@@ -36,7 +56,7 @@ impl SeedableRng for MswsRng {
         // The constant s should be set to a random 64-bit pattern with the
         // upper 32 bits non-zero and the least significant bit set to 1
         let stream = seed_u64[0] | 1;
-        if stream & 0xffffffff_00000000 == 0 { panic!("bad seed: high bits are zero"); }
+        if !Self::is_valid_seed(&seed) { panic!("bad seed: high bits are zero"); }
         Self { x: seed_u64[1], w: 0, s: stream }
     }
 
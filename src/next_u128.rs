@@ -0,0 +1,95 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! 128-bit output.
+
+use rand_core::{RngCore, SeedableRng};
+use rand_core::block::{BlockRng64, BlockRngCore};
+
+use super::*;
+
+/// Generate 128 random bits at once, for example for UUIDs or hash keys.
+pub trait NextU128: RngCore {
+    /// Return the next 128 bits.
+    ///
+    /// The default implementation calls `next_u64` twice, and uses the first
+    /// result as the low half. Generators may override it with a faster
+    /// implementation. If that returns different values than the default, for
+    /// example because it produces 128 bits from a single step, the generator
+    /// documents it.
+    #[inline]
+    fn next_u128(&mut self) -> u128 {
+        let low = self.next_u64();
+        let high = self.next_u64();
+        u128::from(high) << 64 | u128::from(low)
+    }
+}
+
+// Unused if all generator families are disabled.
+#[allow(unused_macros)]
+macro_rules! next_u128_fallback {
+    ($($rng:ty),*) => {
+        $(impl NextU128 for $rng {})*
+    }
+}
+
+// The PCG generators implement `NextU128` in their own module. The other
+// generators have no 128-bit output function, and use two calls to `next_u64`.
+#[cfg(feature = "ci")]
+next_u128_fallback!(CiRng);
+#[cfg(feature = "gj")]
+next_u128_fallback!(GjRng, Gj8Rng, Gj16Rng);
+#[cfg(feature = "jsf")]
+next_u128_fallback!(Jsf8Rng, Jsf16Rng, Jsf32Rng, Jsf64Rng);
+#[cfg(feature = "kiss")]
+next_u128_fallback!(Kiss32Rng, Kiss64Rng);
+#[cfg(feature = "lfsr")]
+next_u128_fallback!(Lfsr113Rng, Lfsr258Rng);
+#[cfg(feature = "msws")]
+next_u128_fallback!(MswsRng);
+#[cfg(feature = "pcg")]
+next_u128_fallback!(MwpRng);
+#[cfg(feature = "sapparot")]
+next_u128_fallback!(Sapparot32Rng, Sapparot64Rng);
+#[cfg(feature = "sfc")]
+next_u128_fallback!(Sfc8Rng, Sfc16Rng, Sfc32Rng, Sfc64Rng);
+#[cfg(feature = "velox")]
+next_u128_fallback!(Velox3bRng);
+#[cfg(feature = "weyl")]
+next_u128_fallback!(WeylRng);
+#[cfg(feature = "xorshift")]
+next_u128_fallback!(Xorshift128PlusRng, XorshiftMt32Rng, XorshiftMt64Rng);
+#[cfg(feature = "xoroshiro")]
+next_u128_fallback!(Xoroshiro128PlusRng, Xoroshiro128PlusPlusRng,
+                    Xoroshiro128StarStarRng,
+                    Xoroshiro64PlusRng, Xoroshiro64StarStarRng,
+                    XoroshiroMt32of128Rng, XoroshiroMt64of128Rng);
+#[cfg(feature = "xsm")]
+next_u128_fallback!(Xsm32Rng, Xsm64Rng);
+
+#[cfg(feature = "xorshift")]
+impl<const A: u32, const B: u32, const C: u32> NextU128
+    for Xorshift32Engine<A, B, C> {}
+#[cfg(feature = "xorshift")]
+impl<const A: u32, const B: u32, const C: u32> NextU128
+    for Xorshift64Engine<A, B, C> {}
+#[cfg(feature = "xorshift")]
+impl<const A: u32, const B: u32, const C: u32> NextU128
+    for Xorshift128_32Engine<A, B, C> {}
+#[cfg(feature = "xorshift")]
+impl<const A: u32, const B: u32, const C: u32> NextU128
+    for Xorshift128_64Engine<A, B, C> {}
+
+// Covers the interleaved generators.
+impl<R: BlockRngCore<Item = u64>> NextU128 for BlockRng64<R> {}
+
+impl<A: RngCore, B: RngCore, C: Combiner> NextU128 for CombinedRng<A, B, C> {}
+impl<R: SeedableRng + RngCore, S: RngCore> NextU128
+    for ReseedingSmallRng<R, S> {}
+impl<R: RngCore> NextU128 for TransformRng<R> {}
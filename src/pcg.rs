@@ -10,121 +10,127 @@
 //! PCG random number generators
 
 use rand_core::{RngCore, SeedableRng, Error, impls, le};
+use core::marker::PhantomData;
 
-/// A PCG random number generator (XSH 64/32 (LCG) variant).
+use crate::next_u128::NextU128;
+use crate::rand_digits::weyl_constant;
+
+/// A generic PCG random number generator.
 ///
-/// Permuted Congruential Generators, "xorshift high (bits), random rotation"
-/// using an underlying Linear congruential generator
+/// Permuted Congruential Generators consist of a state transition `S`, a
+/// linear or multiplicative congruential generator, and an output function
+/// `O` that permutes the state into the output.
+///
+/// The output function is applied to the state before it is advanced, so
+/// calculating the output and the next state can happen in parallel.
+///
+/// State transitions: `Lcg64`, `Mcg64`, `Lcg128`, `Mcg128`.
+/// Output functions: `XshRr`, `XslRr`, `RxsMXs`, `Dxsm`.
 #[derive(Clone)]
-pub struct PcgXsh64LcgRng {
-    state: u64,
-    increment: u64,
+pub struct Pcg<S, O> {
+    state: S,
+    output: PhantomData<O>,
 }
 
-impl SeedableRng for PcgXsh64LcgRng {
-    type Seed = [u8; 16];
+/// The state transition of a PCG random number generator.
+pub trait PcgState {
+    /// The internal state, as passed to the output function.
+    type State: Copy;
+    /// Seed type, as used by `SeedableRng`.
+    type Seed: Sized + Default + AsMut<[u8]>;
 
-    fn from_seed(seed: Self::Seed) -> Self {
-        let mut seed_u64 = [0u64; 2];
-        le::read_u64_into(&seed, &mut seed_u64);
-        // We only have to make sure increment is odd.
-        let mut ctx = Self { state: seed_u64[0],
-                             increment: seed_u64[1] | 1 };
-        // Prepare for the first round
-        ctx.state = ctx.state.wrapping_mul(6364136223846793005)
-                             .wrapping_add(ctx.increment);
-        ctx
+    /// Create a new state from a seed, ready for the first round.
+    fn from_seed(seed: Self::Seed) -> Self;
+
+    /// Advance the state, and return the state before the step.
+    fn step(&mut self) -> Self::State;
+
+    /// Advance the state by two steps, and return the states before each step.
+    fn step2(&mut self) -> (Self::State, Self::State) {
+        let first = self.step();
+        (first, self.step())
     }
 }
 
-impl RngCore for PcgXsh64LcgRng {
-    #[inline]
-    fn next_u32(&mut self) -> u32 {
-        let state = self.state;
-        // prepare the LCG for the next round
-        self.state = state.wrapping_mul(6364136223846793005)
-                          .wrapping_add(self.increment);
+/// An output function of a PCG random number generator, turning internal
+/// state of type `T` into an output word.
+pub trait PcgOutput<T> {
+    /// The output word, `u32` or `u64`.
+    type Output: PcgWord;
 
-        // output function XSH RR: xorshift high (bits), followed by a random rotate
-        // good for 64-bit state, 32-bit output
-        const IN_BITS: u32 = 64;
-        const OUT_BITS: u32 = 32;
-        const OP_BITS: u32 = 5; // log2(OUT_BITS)
+    /// Whether `output_u128` turns a single state into 128 bits of output.
+    const WIDE_OUTPUT: bool = false;
 
-        const ROTATE: u32 = IN_BITS - OP_BITS; // 59
-        const XSHIFT: u32 = (OUT_BITS + OP_BITS) / 2; // 18
-        const SPARE: u32 = IN_BITS - OUT_BITS - OP_BITS; // 27
+    fn output(state: T) -> Self::Output;
 
-        let xsh = (((state >> XSHIFT) ^ state) >> SPARE) as u32;
-        xsh.rotate_right((state >> ROTATE) as u32)
+    /// Turn a single state into 128 bits of output. Only used by `next_u128`
+    /// if `WIDE_OUTPUT` is `true`.
+    #[inline(always)]
+    fn output_u128(state: T) -> u128 {
+        u128::from(Self::output(state).to_u64())
     }
+}
 
-    #[inline]
-    fn next_u64(&mut self) -> u64 {
-       impls::next_u64_via_u32(self)
-    }
+/// The output word of a PCG random number generator.
+pub trait PcgWord: Copy {
+    /// Size of the word in bits.
+    const BITS: u32;
 
-    fn fill_bytes(&mut self, dest: &mut [u8]) {
-        impls::fill_bytes_via_next(self, dest)
-    }
+    /// The low 32 bits of the word.
+    fn low_u32(self) -> u32;
 
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        Ok(self.fill_bytes(dest))
-    }
+    /// The word, zero-extended to 64 bits.
+    fn to_u64(self) -> u64;
 }
 
+impl PcgWord for u32 {
+    const BITS: u32 = 32;
+    #[inline(always)]
+    fn low_u32(self) -> u32 { self }
+    #[inline(always)]
+    fn to_u64(self) -> u64 { u64::from(self) }
+}
 
+impl PcgWord for u64 {
+    const BITS: u32 = 64;
+    #[inline(always)]
+    fn low_u32(self) -> u32 { self as u32 }
+    #[inline(always)]
+    fn to_u64(self) -> u64 { self }
+}
 
-/// A PCG random number generator (XSL 64/32 (LCG) variant).
-///
-/// Permuted Congruential Generators, "xorshift low (bits), random rotation"
-/// using an underlying Linear congruential generator
-#[derive(Clone)]
-pub struct PcgXsl64LcgRng {
-    state: u64,
-    increment: u64,
+impl<S: PcgState, O: PcgOutput<S::State>> Pcg<S, O> {
+    #[inline]
+    fn next_word(&mut self) -> O::Output {
+        O::output(self.state.step())
+    }
 }
 
-impl SeedableRng for PcgXsl64LcgRng {
-    type Seed = [u8; 16];
+impl<S, O> SeedableRng for Pcg<S, O>
+    where S: PcgState, O: PcgOutput<S::State>
+{
+    type Seed = S::Seed;
 
     fn from_seed(seed: Self::Seed) -> Self {
-        let mut seed_u64 = [0u64; 2];
-        le::read_u64_into(&seed, &mut seed_u64);
-        // We only have to make sure increment is odd.
-        let mut ctx = Self { state: seed_u64[0],
-                             increment: seed_u64[1] | 1 };
-        // Prepare for the first round
-        ctx.state = ctx.state.wrapping_mul(6364136223846793005)
-                             .wrapping_add(ctx.increment);
-        ctx
+        Self { state: S::from_seed(seed), output: PhantomData }
     }
 }
 
-impl RngCore for PcgXsl64LcgRng {
+impl<S, O> RngCore for Pcg<S, O>
+    where S: PcgState, O: PcgOutput<S::State>
+{
     #[inline]
     fn next_u32(&mut self) -> u32 {
-        let state = self.state;
-        // prepare the LCG for the next round
-        self.state = state.wrapping_mul(6364136223846793005)
-                          .wrapping_add(self.increment);
-
-        // Output function XSL RR ("xorshift low (bits), random rotation"):
-        const IN_BITS: u32 = 64;
-        const OUT_BITS: u32 = 32;
-        const SPARE_BITS: u32 = IN_BITS - OUT_BITS;
-        const OP_BITS: u32 = 5; // log2(OUT_BITS)
-
-        const XSHIFT: u32 = (SPARE_BITS + OUT_BITS) / 2; // 32
-        const ROTATE: u32 = IN_BITS - OP_BITS; // 59
-
-        let xsl = ((state >> XSHIFT) as u32) ^ (state as u32);
-        xsl.rotate_right((state >> ROTATE) as u32)
+        self.next_word().low_u32()
     }
 
     #[inline]
     fn next_u64(&mut self) -> u64 {
-       impls::next_u64_via_u32(self)
+        if O::Output::BITS >= 64 {
+            self.next_word().to_u64()
+        } else {
+            impls::next_u64_via_u32(self)
+        }
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
@@ -132,51 +138,248 @@ impl RngCore for PcgXsl64LcgRng {
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        Ok(self.fill_bytes(dest))
+        self.fill_bytes(dest);
+        Ok(())
     }
 }
 
 
 
-/// A PCG random number generator (XSL 128/64 (MCG) variant).
+impl<S, O> NextU128 for Pcg<S, O>
+    where S: PcgState, O: PcgOutput<S::State>
+{
+    // With a wide output function one state gives all 128 bits. Otherwise
+    // this is the same output as the default implementation, but all states
+    // come from `step2`, so the outputs can be calculated in parallel.
+    #[inline]
+    fn next_u128(&mut self) -> u128 {
+        if O::WIDE_OUTPUT {
+            O::output_u128(self.state.step())
+        } else if O::Output::BITS >= 64 {
+            let (s0, s1) = self.state.step2();
+            let low = O::output(s0).to_u64();
+            let high = O::output(s1).to_u64();
+            u128::from(high) << 64 | u128::from(low)
+        } else {
+            let (s0, s1) = self.state.step2();
+            let (s2, s3) = self.state.step2();
+            u128::from(O::output(s3).low_u32()) << 96 |
+            u128::from(O::output(s2).low_u32()) << 64 |
+            u128::from(O::output(s1).low_u32()) << 32 |
+            u128::from(O::output(s0).low_u32())
+        }
+    }
+}
+
+
+
+const MULTIPLIER_64: u64 = 6364136223846793005;
+const MULTIPLIER_128: u128 = 2549297995355413924u128 << 64 | 4865540595714422341;
+// The multipliers for two steps at once.
+const MULTIPLIER_64_SQ: u64 = MULTIPLIER_64.wrapping_mul(MULTIPLIER_64);
+const MULTIPLIER_128_SQ: u128 = MULTIPLIER_128.wrapping_mul(MULTIPLIER_128);
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    let mut x = [0u64; 1];
+    le::read_u64_into(bytes, &mut x);
+    x[0]
+}
+
+fn read_u128(bytes: &[u8]) -> u128 {
+    let mut x = [0u64; 2];
+    le::read_u64_into(bytes, &mut x);
+    (x[0] as u128) << 64 | (x[1] as u128)
+}
+
+macro_rules! lcg_state {
+    ($name:ident, $ty:ty, $bits:expr, $multiplier:expr, $multiplier_sq:expr,
+     $read:ident) => {
+        #[doc = concat!("A ", stringify!($bits), "-bit linear congruential ",
+                        "generator, as state transition of a PCG random ",
+                        "number generator.")]
+        #[derive(Clone)]
+        pub struct $name {
+            state: $ty,
+            increment: $ty,
+        }
+
+        impl $name {
+            /// Use `state` and `increment` as they are, without the step taken
+            /// by `from_seed`. `increment` is made odd.
+            pub const fn new_unmixed(state: $ty, increment: $ty) -> Self {
+                Self { state, increment: increment | 1 }
+            }
+        }
+
+        impl<O> Pcg<$name, O> {
+            /// Construct the generator from its state, without the step taken
+            /// by `from_seed`. Being a `const fn`, this can initialize a
+            /// `static`.
+            pub const fn new_unmixed(state: $ty, increment: $ty) -> Self {
+                Pcg { state: $name::new_unmixed(state, increment),
+                      output: PhantomData }
+            }
+        }
+
+        impl PcgState for $name {
+            type State = $ty;
+            type Seed = [u8; $bits / 4];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                // We only have to make sure increment is odd.
+                let mut ctx = Self { state: $read(&seed[..$bits / 8]),
+                                     increment: $read(&seed[$bits / 8..]) | 1 };
+                // Prepare for the first round
+                ctx.step();
+                ctx
+            }
+
+            #[inline]
+            fn step(&mut self) -> $ty {
+                let state = self.state;
+                self.state = state.wrapping_mul($multiplier)
+                                  .wrapping_add(self.increment);
+                state
+            }
+
+            #[inline]
+            fn step2(&mut self) -> ($ty, $ty) {
+                // s2 = s0 * M^2 + inc * (M + 1), so both new states only
+                // depend on s0.
+                let s0 = self.state;
+                let s1 = s0.wrapping_mul($multiplier)
+                           .wrapping_add(self.increment);
+                let increment2 =
+                    self.increment.wrapping_mul($multiplier.wrapping_add(1));
+                self.state = s0.wrapping_mul($multiplier_sq)
+                               .wrapping_add(increment2);
+                (s0, s1)
+            }
+        }
+    }
+}
+
+macro_rules! mcg_state {
+    ($name:ident, $ty:ty, $bits:expr, $multiplier:expr, $multiplier_sq:expr,
+     $read:ident) => {
+        #[doc = concat!("A ", stringify!($bits), "-bit multiplicative ",
+                        "congruential generator, as state transition of a PCG ",
+                        "random number generator.")]
+        #[derive(Clone)]
+        pub struct $name {
+            state: $ty,
+        }
+
+        impl $name {
+            /// Use `state` as it is, without the step taken by `from_seed`.
+            /// `state` is made odd, because an even state shortens the period.
+            pub const fn new_unmixed(state: $ty) -> Self {
+                Self { state: state | 1 }
+            }
+        }
+
+        impl<O> Pcg<$name, O> {
+            /// Construct the generator from its state, without the step taken
+            /// by `from_seed`. Being a `const fn`, this can initialize a
+            /// `static`.
+            pub const fn new_unmixed(state: $ty) -> Self {
+                Pcg { state: $name::new_unmixed(state), output: PhantomData }
+            }
+        }
+
+        impl PcgState for $name {
+            type State = $ty;
+            type Seed = [u8; $bits / 8];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                let mut ctx = Self { state: $read(&seed) };
+                // Prepare for the first round
+                ctx.step();
+                ctx
+            }
+
+            #[inline]
+            fn step(&mut self) -> $ty {
+                let state = self.state;
+                self.state = state.wrapping_mul($multiplier);
+                state
+            }
+
+            #[inline]
+            fn step2(&mut self) -> ($ty, $ty) {
+                let s0 = self.state;
+                self.state = s0.wrapping_mul($multiplier_sq);
+                (s0, s0.wrapping_mul($multiplier))
+            }
+        }
+    }
+}
+
+lcg_state!(Lcg64, u64, 64, MULTIPLIER_64, MULTIPLIER_64_SQ, read_u64);
+mcg_state!(Mcg64, u64, 64, MULTIPLIER_64, MULTIPLIER_64_SQ, read_u64);
+lcg_state!(Lcg128, u128, 128, MULTIPLIER_128, MULTIPLIER_128_SQ, read_u128);
+mcg_state!(Mcg128, u128, 128, MULTIPLIER_128, MULTIPLIER_128_SQ, read_u128);
+
+
+
+/// Output function XSH RR: xorshift high (bits), followed by a random rotate.
 ///
-/// Permuted Congruential Generators, "xorshift low (bits), random rotation"
-/// using an underlying multiplicative congruential generator
+/// Good for 64-bit state, 32-bit output.
 #[derive(Clone)]
-pub struct PcgXsl128McgRng {
-    state: u128,
-}
+pub struct XshRr;
 
-const MULTIPLIER: u128 = 2549297995355413924u128 << 64 | 4865540595714422341;
+impl PcgOutput<u64> for XshRr {
+    type Output = u32;
 
-impl SeedableRng for PcgXsl128McgRng {
-    type Seed = [u8; 16];
+    #[inline(always)]
+    fn output(state: u64) -> u32 {
+        const IN_BITS: u32 = 64;
+        const OUT_BITS: u32 = 32;
+        const OP_BITS: u32 = 5; // log2(OUT_BITS)
 
-    fn from_seed(seed: Self::Seed) -> Self {
-        let mut seed_u64 = [0u64; 2];
-        le::read_u64_into(&seed, &mut seed_u64);
-        // We only have to make sure increment is odd.
-        let mut ctx = Self { state: (seed_u64[0] as u128) << 64 |
-                                    (seed_u64[1] as u128) };
-        // Prepare for the first round
-        ctx.state = ctx.state.wrapping_mul(MULTIPLIER);
-        ctx
+        const ROTATE: u32 = IN_BITS - OP_BITS; // 59
+        const XSHIFT: u32 = (OUT_BITS + OP_BITS) / 2; // 18
+        const SPARE: u32 = IN_BITS - OUT_BITS - OP_BITS; // 27
+
+        let xsh = (((state >> XSHIFT) ^ state) >> SPARE) as u32;
+        xsh.rotate_right((state >> ROTATE) as u32)
     }
 }
 
-impl RngCore for PcgXsl128McgRng {
-    #[inline]
-    fn next_u32(&mut self) -> u32 {
-        self.next_u64() as u32
+/// Output function XSL RR: xorshift low (bits), followed by a random rotate.
+///
+/// Cheaper than XSH RR, intended for 128-bit state, 64-bit output.
+///
+/// With 128-bit state `next_u128` uses XSL RR RR, which gives 128 bits from a
+/// single state: the low half is the XSL RR output, and the high half is the
+/// high half of the state with another random rotate. So it advances the state
+/// only once, and returns different values than two calls to `next_u64`.
+#[derive(Clone)]
+pub struct XslRr;
+
+impl PcgOutput<u64> for XslRr {
+    type Output = u32;
+
+    #[inline(always)]
+    fn output(state: u64) -> u32 {
+        const IN_BITS: u32 = 64;
+        const OUT_BITS: u32 = 32;
+        const SPARE_BITS: u32 = IN_BITS - OUT_BITS;
+        const OP_BITS: u32 = 5; // log2(OUT_BITS)
+
+        const XSHIFT: u32 = (SPARE_BITS + OUT_BITS) / 2; // 32
+        const ROTATE: u32 = IN_BITS - OP_BITS; // 59
+
+        let xsl = ((state >> XSHIFT) as u32) ^ (state as u32);
+        xsl.rotate_right((state >> ROTATE) as u32)
     }
+}
 
-    #[inline]
-    fn next_u64(&mut self) -> u64 {
-        let state = self.state;
-        // prepare for the next round
-        self.state = state.wrapping_mul(MULTIPLIER);
+impl PcgOutput<u128> for XslRr {
+    type Output = u64;
 
-        // Output function XSL RR ("xorshift low (bits), random rotation"):
+    #[inline(always)]
+    fn output(state: u128) -> u64 {
         const IN_BITS: u32 = 128;
         const OUT_BITS: u32 = 64;
         const SPARE_BITS: u32 = IN_BITS - OUT_BITS;
@@ -189,21 +392,113 @@ impl RngCore for PcgXsl128McgRng {
         xsl.rotate_right((state >> ROTATE) as u32)
     }
 
-    fn fill_bytes(&mut self, dest: &mut [u8]) {
-        impls::fill_bytes_via_next(self, dest)
+    const WIDE_OUTPUT: bool = true;
+
+    // XSL RR RR 128/128.
+    #[inline(always)]
+    fn output_u128(state: u128) -> u128 {
+        let low = Self::output(state);
+        let high = ((state >> 64) as u64).rotate_right(low as u32 & 63);
+        u128::from(high) << 64 | u128::from(low)
     }
+}
 
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        Ok(self.fill_bytes(dest))
+/// Output function RXS M XS: random xorshift, mcg multiply, fixed xorshift.
+///
+/// Output is as wide as the state. Because the output function is a
+/// bijection, with an LCG every output appears exactly once per period.
+#[derive(Clone)]
+pub struct RxsMXs;
+
+macro_rules! rxs_m_xs {
+    ($ty:ty, $bits:expr, $op_bits:expr, $xshift:expr, $multiplier:expr) => {
+        impl PcgOutput<$ty> for RxsMXs {
+            type Output = $ty;
+
+            #[inline(always)]
+            fn output(mut state: $ty) -> $ty {
+                const BITS: u32 = $bits;
+                const OP_BITS: u32 = $op_bits;
+                const XSHIFT: u32 = $xshift; // (2 * BITS + 2) / 3
+                const MASK: $ty = (1 << OP_BITS) - 1;
+
+                let rshift = (state >> (BITS - OP_BITS)) & MASK;
+                state ^= state >> (OP_BITS + rshift as u32);
+                state = state.wrapping_mul($multiplier);
+                state ^ (state >> XSHIFT)
+            }
+        }
     }
 }
 
+rxs_m_xs!(u32, 32, 4, 22, 277803737);
+rxs_m_xs!(u64, 64, 5, 43, 12605985483714917081);
+
+/// Output function DXSM: double xorshift multiply.
+///
+/// Good for 128-bit state, 64-bit output. Stronger than XSL RR, at the cost of
+/// two multiplications.
+#[derive(Clone)]
+pub struct Dxsm;
+
+impl PcgOutput<u128> for Dxsm {
+    type Output = u64;
+
+    #[inline(always)]
+    fn output(state: u128) -> u64 {
+        const CHEAP_MULTIPLIER: u64 = 0xda942042e4dd58b5;
+
+        let mut hi = (state >> 64) as u64;
+        let lo = state as u64 | 1;
+        hi ^= hi >> 32;
+        hi = hi.wrapping_mul(CHEAP_MULTIPLIER);
+        hi ^= hi >> 48;
+        hi.wrapping_mul(lo)
+    }
+}
+
+
+
+/// A PCG random number generator (XSH 64/32 (LCG) variant).
+///
+/// Permuted Congruential Generators, "xorshift high (bits), random rotation"
+/// using an underlying Linear congruential generator
+pub type PcgXsh64LcgRng = Pcg<Lcg64, XshRr>;
+
+/// A PCG random number generator (XSL 64/32 (LCG) variant).
+///
+/// Permuted Congruential Generators, "xorshift low (bits), random rotation"
+/// using an underlying Linear congruential generator
+pub type PcgXsl64LcgRng = Pcg<Lcg64, XslRr>;
+
+/// A PCG random number generator (XSL 128/64 (MCG) variant).
+///
+/// Permuted Congruential Generators, "xorshift low (bits), random rotation"
+/// using an underlying multiplicative congruential generator
+pub type PcgXsl128McgRng = Pcg<Mcg128, XslRr>;
+
 
 
 #[derive(Clone)]
 pub struct MwpRng {
     m: u64,
     w: u64,
+    increment: u64,
+}
+
+impl MwpRng {
+    /// The Weyl increment used by `from_seed`.
+    pub const DEFAULT_INCREMENT: u64 = 1442695040888963407;
+
+    /// Construct the generator for stream `index`, starting from `seed`.
+    ///
+    /// The Weyl increment is derived from `index` like `init_rand_digits` of
+    /// the MSWS reference code: an odd constant where each 32-bit half has 8
+    /// different non-zero hexadecimal digits. The same index always gives the
+    /// same increment.
+    pub fn with_stream_index(seed: u64, index: u64) -> Self {
+        Self { m: seed | 1, w: 0, increment: weyl_constant(index) }
+    }
 }
 
 impl SeedableRng for MwpRng {
@@ -212,7 +507,11 @@ impl SeedableRng for MwpRng {
     fn from_seed(seed: Self::Seed) -> Self {
         let mut seed_u64 = [0u64; 2];
         le::read_u64_into(&seed, &mut seed_u64);
-        Self { m: seed_u64[0] | 1, w: seed_u64[1] }
+        Self {
+            m: seed_u64[0] | 1,
+            w: seed_u64[1],
+            increment: Self::DEFAULT_INCREMENT,
+        }
     }
 }
 
@@ -220,20 +519,8 @@ impl RngCore for MwpRng {
     #[inline]
     fn next_u32(&mut self) -> u32 {
         self.m = self.m.wrapping_mul(6364136223846793005);
-        self.w = self.w.wrapping_add(1442695040888963407);
-        let state = self.m ^ self.w;
-
-        // output function XSH RR: xorshift high (bits), followed by a random rotate
-        const IN_BITS: u32 = 64;
-        const OUT_BITS: u32 = 32;
-        const OP_BITS: u32 = 5; // log2(OUT_BITS)
-
-        const ROTATE: u32 = IN_BITS - OP_BITS; // 59
-        const XSHIFT: u32 = (OUT_BITS + OP_BITS) / 2; // 18
-        const SPARE: u32 = IN_BITS - OUT_BITS - OP_BITS; // 27
-
-        let xsh = (((state >> XSHIFT) ^ state) >> SPARE) as u32;
-        xsh.rotate_right((state >> ROTATE) as u32)
+        self.w = self.w.wrapping_add(self.increment);
+        XshRr::output(self.m ^ self.w)
     }
 
     #[inline]
@@ -241,11 +528,12 @@ impl RngCore for MwpRng {
         // MCG
         self.m = self.m.wrapping_mul(6364136223846793005);
         // Weyl sequence
-        self.w = self.w.wrapping_add(1442695040888963407);
+        self.w = self.w.wrapping_add(self.increment);
         let mut state = self.m ^ self.w;
 
         // output function RXS M XS:
         // random xorshift, mcg multiply, fixed xorshift
+        // Note: unlike `RxsMXs` this multiplies with the LCG multiplier.
         const BITS: u64 = 64;
         const OP_BITS: u64 = 5; // log2(BITS)
         const MASK: u64 = BITS - 1;
@@ -264,3 +552,151 @@ impl RngCore for MwpRng {
         Ok(self.fill_bytes(dest))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand_core::{RngCore, SeedableRng};
+    use super::*;
+
+    const STATE: u128 = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+
+    #[test]
+    fn xsl_rr_rr() {
+        let mut rng = Pcg::<Mcg128, XslRr>::new_unmixed(STATE);
+        let mut copy = rng.clone();
+        let x = rng.next_u128();
+        let low = copy.next_u64();
+        let high = ((STATE | 1) >> 64) as u64;
+        assert_eq!(x as u64, low);
+        assert_eq!((x >> 64) as u64, high.rotate_right(low as u32 & 63));
+        // Only a single step.
+        assert_eq!(rng.next_u64(), copy.next_u64());
+    }
+
+    // The output of `MwpRng::from_seed` from before the increment could be
+    // chosen with `with_stream_index`.
+    #[test]
+    fn mwp_from_seed() {
+        let mut seed = [0u8; 16];
+        seed[..8].copy_from_slice(&0x0123_4567_89ab_cdef_u64.to_le_bytes());
+        seed[8..].copy_from_slice(&0xfedc_ba98_7654_3210_u64.to_le_bytes());
+        let mut rng = MwpRng::from_seed(seed);
+        let expected = [7760011306789180814, 7141642722375938527,
+                        190111803145665657, 1633676200835820795];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u64(), x);
+        }
+        assert_eq!(rng.next_u32(), 636308147);
+        assert_eq!(rng.next_u32(), 1244727683);
+    }
+
+    // The output of `from_seed` of the original implementation.
+    #[test]
+    fn from_seed() {
+        let mut seed = [0u8; 16];
+        for (i, x) in seed.iter_mut().enumerate() {
+            *x = i as u8 + 1;
+        }
+
+        let mut rng = PcgXsh64LcgRng::from_seed(seed);
+        for &x in [0x4f1f04a0, 0xb43c2576, 0xedabe6d5, 0xf0e038aa].iter() {
+            assert_eq!(rng.next_u32(), x);
+        }
+
+        let mut rng = PcgXsl64LcgRng::from_seed(seed);
+        for &x in [0xc1473500, 0xc39a52be, 0x7b21ce35, 0x30202831].iter() {
+            assert_eq!(rng.next_u32(), x);
+        }
+
+        let mut rng = PcgXsl128McgRng::from_seed(seed);
+        let expected = [0x005fc59731066494, 0x5158046355a1dbb6,
+                        0xf2059081c038a751, 0x3cfd9694d73b847f];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u64(), x);
+        }
+    }
+
+    // The state after `pcg_setseq_64_srandom_r(rng, 42, 54)` of pcg-c.
+    fn srandom_64() -> (u64, u64) {
+        let increment: u64 = 54 << 1 | 1;
+        let state = (increment + 42).wrapping_mul(MULTIPLIER_64)
+                                    .wrapping_add(increment);
+        (state, increment)
+    }
+
+    // The same for `pcg_setseq_128_srandom_r`. The 128-bit generators of
+    // pcg-c output the state after the step, so take one more step.
+    fn srandom_128() -> (u128, u128) {
+        let increment: u128 = 54 << 1 | 1;
+        let state = (increment + 42).wrapping_mul(MULTIPLIER_128)
+                                    .wrapping_add(increment)
+                                    .wrapping_mul(MULTIPLIER_128)
+                                    .wrapping_add(increment);
+        (state, increment)
+    }
+
+    // The demo output of pcg-c and pcg-cpp for seed 42 and stream 54.
+    #[test]
+    fn pcg32_reference() {
+        let (state, increment) = srandom_64();
+        let mut rng = Pcg::<Lcg64, XshRr>::new_unmixed(state, increment);
+        let expected = [0xa15c02b7, 0x7b47f409, 0xba1d3330,
+                        0x83d2f293, 0xbfa4784b, 0xcbed606e];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u32(), x);
+        }
+    }
+
+    #[test]
+    fn pcg64_reference() {
+        let (state, increment) = srandom_128();
+        let mut rng = Pcg::<Lcg128, XslRr>::new_unmixed(state, increment);
+        let expected = [0x86b1da1d72062b68, 0x1304aa46c9853d39,
+                        0xa3670e9e0dd50358, 0xf9090e529a7dae00,
+                        0xc85b9fd837996f2c, 0x606121f8e3919196];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u64(), x);
+        }
+    }
+
+    // `pcg64_fast` with seed 42, which pcg-cpp makes `42 | 3`.
+    #[test]
+    fn pcg64_fast_reference() {
+        let state = 43u128.wrapping_mul(MULTIPLIER_128);
+        let mut rng = Pcg::<Mcg128, XslRr>::new_unmixed(state);
+        let expected = [0x63b4a3a813ce700a, 0x382954200617ab24,
+                        0xa7fd85ae3fe950ce, 0xd715286aa2887737,
+                        0x60c92fee2e59f32c, 0x84c4e96beff30017];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u64(), x);
+        }
+    }
+
+    // `pcg_output_rxs_m_xs_64_64` of pcg-c, with seed 42 and stream 54.
+    #[test]
+    fn rxs_m_xs_reference() {
+        let (state, increment) = srandom_64();
+        let mut rng = Pcg::<Lcg64, RxsMXs>::new_unmixed(state, increment);
+        let expected = [0xe1cbc180b69606bb, 0x6573bce7abaee684,
+                        0xc744f07442006076, 0x9e9f98ccbd60b8fc,
+                        0xde693821ee9629ae, 0x263cc2cdc66ebc25];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u64(), x);
+        }
+    }
+
+    // `dxsm_mixin` of pcg-cpp on the 128-bit LCG with the full multiplier,
+    // with seed 42 and stream 54. Not the same as `PCG64DXSM` of numpy, which
+    // uses the cheap multiplier for the LCG as well.
+    #[test]
+    fn dxsm_reference() {
+        let (state, increment) = srandom_128();
+        let mut rng = Pcg::<Lcg128, Dxsm>::new_unmixed(state, increment);
+        let expected = [0x9b15182e3c8bfc16, 0xaf236e44ebf54fcd,
+                        0x6e5c3eecf94b69a1, 0x80710a3593aac92a,
+                        0x8d8df2a6c189fa2b, 0x13eccf7e6a2d8d73];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u64(), x);
+        }
+    }
+}
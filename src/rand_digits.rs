@@ -0,0 +1,113 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Weyl sequence constants with an irregular bit pattern.
+
+// The Weyl constant of the MSWS reference code, used by the local generator.
+const LOCAL_WEYL: u64 = 0xb5ad_4ece_da1c_e2a9;
+
+/// Derive the Weyl constant for stream `index`, following `init_rand_digits`
+/// of the MSWS reference code.
+///
+/// The constant is odd, and each 32-bit half consists of 8 different non-zero
+/// hexadecimal digits. This avoids constants with long runs of equal bits,
+/// which make poor Weyl sequences. Like the reference code the digits are
+/// chosen from the top down, and the last digit of the high half is not
+/// repeated at the top of the low half, so no two adjacent digits are equal.
+///
+/// The random digits come from a local MSWS generator seeded with `index`,
+/// not from the table of seeds of the reference code, so the constants are not
+/// the same. Different indices give different constants with high
+/// probability, but not with certainty.
+pub(crate) fn weyl_constant(index: u64) -> u64 {
+    // A local MSWS generator, warmed up so small indices are mixed well.
+    let mut w = index.wrapping_mul(LOCAL_WEYL);
+    let mut x = w;
+    let mut next = || {
+        x = x.wrapping_mul(x);
+        w = w.wrapping_add(LOCAL_WEYL);
+        x = x.wrapping_add(w);
+        x = x.rotate_left(32);
+        x as u32
+    };
+    for _ in 0..16 {
+        next();
+    }
+
+    // The lowest digit is odd, so the constant is odd.
+    let low = u64::from(next() % 8) * 2 + 1;
+    let mut constant = low;
+    let mut used: u16 = 1 << low;
+    let mut shift = 60;
+    while shift > 0 {
+        let word = next();
+        for i in (0..32).step_by(4) {
+            let digit = (word >> i) & 0xf;
+            if digit != 0 && used & (1 << digit) == 0 {
+                used |= 1 << digit;
+                constant |= u64::from(digit) << shift;
+                shift -= 4;
+                // Start over with the digits of the low half, which already
+                // has the lowest digit. Keep the previous digit, so it is not
+                // repeated on the other side of the boundary.
+                if shift == 28 { used = 1 << low | 1 << digit; }
+                if shift == 0 { break; }
+            }
+        }
+    }
+    constant
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Each 32-bit half consists of 8 different non-zero hexadecimal digits.
+    fn valid_digits(half: u32) -> bool {
+        let mut used: u16 = 0;
+        for i in (0..32).step_by(4) {
+            let digit = (half >> i) & 0xf;
+            if digit == 0 || used & (1 << digit) != 0 {
+                return false;
+            }
+            used |= 1 << digit;
+        }
+        true
+    }
+
+    #[test]
+    fn constants_have_the_pattern() {
+        for index in (0..10_000).chain(u64::MAX - 100..=u64::MAX) {
+            let constant = weyl_constant(index);
+            assert_eq!(constant & 1, 1, "index {}", index);
+            assert!(valid_digits(constant as u32), "index {}", index);
+            assert!(valid_digits((constant >> 32) as u32), "index {}", index);
+        }
+    }
+
+    #[test]
+    fn no_equal_adjacent_digits() {
+        for index in 0..10_000 {
+            let constant = weyl_constant(index);
+            for shift in (0..60).step_by(4) {
+                assert_ne!((constant >> shift) & 0xf,
+                           (constant >> (shift + 4)) & 0xf,
+                           "index {}, bit {}", index, shift);
+            }
+        }
+    }
+
+    #[test]
+    fn constants_are_deterministic() {
+        assert_eq!(weyl_constant(0), 0x674e_2d1b_c9e6_8723);
+        assert_eq!(weyl_constant(1), 0x9218_e4a7_ec62_4813);
+        assert_eq!(weyl_constant(2), 0x42be_5f3d_3f8b_75a9);
+        assert_eq!(weyl_constant(12345), 0x97d3_fc85_27b6_3ec1);
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A registry of all generators by name, for the command line tools and the
+//! C interface.
+
+use rand_core::{RngCore, SeedableRng};
+use std::boxed::Box;
+
+use super::*;
+
+/// A generator in the registry.
+pub struct RngEntry {
+    /// Short name of the generator, as used on the command line.
+    pub name: &'static str,
+    /// Native word size of the generator in bits: 8, 16, 32 or 64.
+    pub word_bits: u32,
+    seed_len: fn() -> usize,
+    seed_is_valid: fn(&[u8]) -> bool,
+    from_seed: fn(&[u8]) -> Box<dyn RngCore>,
+    from_entropy: fn() -> Box<dyn RngCore>,
+}
+
+impl RngEntry {
+    /// Size of the seed in bytes.
+    pub fn seed_len(&self) -> usize {
+        (self.seed_len)()
+    }
+
+    /// Whether `seed` has the right length, and is accepted by the generator.
+    ///
+    /// Most generators accept any seed, but for example `MswsRng` panics on a
+    /// seed with a weak Weyl constant.
+    pub fn is_valid_seed(&self, seed: &[u8]) -> bool {
+        seed.len() == self.seed_len() && (self.seed_is_valid)(seed)
+    }
+
+    /// Create the generator from a seed of exactly `seed_len` bytes.
+    ///
+    /// Returns `None` if the seed is not valid, see `is_valid_seed`.
+    pub fn from_seed(&self, seed: &[u8]) -> Option<Box<dyn RngCore>> {
+        if !self.is_valid_seed(seed) {
+            return None;
+        }
+        Some((self.from_seed)(seed))
+    }
+
+    /// Create the generator, seeded with entropy from
+    /// [`entropy::fill_entropy`](../entropy/fn.fill_entropy.html).
+    /// Invalid seeds are drawn again.
+    ///
+    /// Panics if no source of entropy is available.
+    pub fn from_entropy(&self) -> Box<dyn RngCore> {
+        (self.from_entropy)()
+    }
+}
+
+// These are unused if all generator families are disabled.
+#[allow(dead_code)]
+fn seed_len<R: SeedableRng>() -> usize {
+    R::Seed::default().as_mut().len()
+}
+
+#[allow(dead_code)]
+fn any_seed(_seed: &[u8]) -> bool {
+    true
+}
+
+#[cfg(feature = "msws")]
+fn msws_seed(seed: &[u8]) -> bool {
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(seed);
+    MswsRng::is_valid_seed(&bytes)
+}
+
+#[allow(dead_code)]
+fn from_seed<R: SeedableRng + RngCore + 'static>(bytes: &[u8]) -> Box<dyn RngCore> {
+    let mut seed = R::Seed::default();
+    seed.as_mut().copy_from_slice(bytes);
+    Box::new(R::from_seed(seed))
+}
+
+#[allow(dead_code)]
+fn from_entropy<R: SeedableRng + RngCore + 'static>() -> Box<dyn RngCore> {
+    // `from_rng` instead of `entropy::from_entropy`, because it draws again
+    // if the seed is not valid.
+    match R::from_rng(entropy::EntropySource) {
+        Ok(rng) => Box::new(rng),
+        Err(err) => panic!("from_entropy failed: {}", err),
+    }
+}
+
+#[allow(unused_macros)]
+macro_rules! entry {
+    ($name:expr, $word_bits:expr, $rng:ty) => {
+        entry!($name, $word_bits, $rng, any_seed)
+    };
+    ($name:expr, $word_bits:expr, $rng:ty, $seed_is_valid:expr) => {
+        RngEntry {
+            name: $name,
+            word_bits: $word_bits,
+            seed_len: seed_len::<$rng>,
+            seed_is_valid: $seed_is_valid,
+            from_seed: from_seed::<$rng>,
+            from_entropy: from_entropy::<$rng>,
+        }
+    }
+}
+
+/// All generators, sorted by name.
+pub static GENERATORS: &[RngEntry] = &[
+    #[cfg(feature = "ci")]
+    entry!("ci", 64, CiRng),
+    #[cfg(feature = "gj")]
+    entry!("gj", 64, GjRng),
+    #[cfg(feature = "gj")]
+    entry!("gj16", 16, Gj16Rng),
+    #[cfg(feature = "gj")]
+    entry!("gj8", 8, Gj8Rng),
+    #[cfg(feature = "jsf")]
+    entry!("jsf16", 16, Jsf16Rng),
+    #[cfg(feature = "jsf")]
+    entry!("jsf32", 32, Jsf32Rng),
+    #[cfg(feature = "jsf")]
+    entry!("jsf64", 64, Jsf64Rng),
+    #[cfg(feature = "jsf")]
+    entry!("jsf8", 8, Jsf8Rng),
+    #[cfg(feature = "kiss")]
+    entry!("kiss32", 32, Kiss32Rng),
+    #[cfg(feature = "kiss")]
+    entry!("kiss64", 64, Kiss64Rng),
+    #[cfg(feature = "lfsr")]
+    entry!("lfsr113", 32, Lfsr113Rng),
+    #[cfg(feature = "lfsr")]
+    entry!("lfsr258", 64, Lfsr258Rng),
+    #[cfg(feature = "msws")]
+    entry!("msws", 64, MswsRng, msws_seed),
+    #[cfg(feature = "pcg")]
+    entry!("mwp", 64, MwpRng),
+    #[cfg(feature = "pcg")]
+    entry!("pcg_xsh_64_lcg", 32, PcgXsh64LcgRng),
+    #[cfg(feature = "pcg")]
+    entry!("pcg_xsl_128_mcg", 64, PcgXsl128McgRng),
+    #[cfg(feature = "pcg")]
+    entry!("pcg_xsl_64_lcg", 32, PcgXsl64LcgRng),
+    #[cfg(feature = "sapparot")]
+    entry!("sapparoth_32", 32, Sapparot32Rng),
+    #[cfg(feature = "sapparot")]
+    entry!("sapparoth_64", 64, Sapparot64Rng),
+    #[cfg(feature = "sfc")]
+    entry!("sfc_16", 16, Sfc16Rng),
+    #[cfg(feature = "sfc")]
+    entry!("sfc_32", 32, Sfc32Rng),
+    #[cfg(feature = "sfc")]
+    entry!("sfc_64", 64, Sfc64Rng),
+    #[cfg(feature = "simd")]
+    entry!("sfc_64x4", 64, Sfc64x4Rng),
+    #[cfg(feature = "sfc")]
+    entry!("sfc_8", 8, Sfc8Rng),
+    #[cfg(feature = "velox")]
+    entry!("velox", 32, Velox3bRng),
+    #[cfg(feature = "weyl")]
+    entry!("weyl", 64, WeylRng),
+    #[cfg(feature = "xoroshiro")]
+    entry!("xoroshiro_128_plus", 64, Xoroshiro128PlusRng),
+    #[cfg(feature = "xoroshiro")]
+    entry!("xoroshiro_128_plusplus", 64, Xoroshiro128PlusPlusRng),
+    #[cfg(feature = "xoroshiro")]
+    entry!("xoroshiro_128_starstar", 64, Xoroshiro128StarStarRng),
+    #[cfg(feature = "xoroshiro")]
+    entry!("xoroshiro_64_plus", 32, Xoroshiro64PlusRng),
+    #[cfg(feature = "xoroshiro")]
+    entry!("xoroshiro_64_starstar", 32, Xoroshiro64StarStarRng),
+    #[cfg(feature = "xoroshiro")]
+    entry!("xoroshiro_mt_32of128", 32, XoroshiroMt32of128Rng),
+    #[cfg(feature = "xoroshiro")]
+    entry!("xoroshiro_mt_64of128", 64, XoroshiroMt64of128Rng),
+    #[cfg(feature = "xorshift")]
+    entry!("xorshift_128_32", 32, Xorshift128_32Rng),
+    #[cfg(feature = "xorshift")]
+    entry!("xorshift_128_64", 64, Xorshift128_64Rng),
+    #[cfg(all(feature = "xorshift", feature = "weyl"))]
+    entry!("xorshift_128_64_xor_weyl", 64,
+           CombinedRng<Xorshift128_64Rng, WeylRng, CombineXor>),
+    #[cfg(feature = "xorshift")]
+    entry!("xorshift_128_plus", 64, Xorshift128PlusRng),
+    #[cfg(all(feature = "xorshift", feature = "weyl"))]
+    entry!("xorshift_128_plus_add_weyl", 64,
+           CombinedRng<Xorshift128PlusRng, WeylRng, CombineAdd>),
+    #[cfg(all(feature = "xorshift", feature = "weyl"))]
+    entry!("xorshift_128_plus_xor_weyl", 64,
+           CombinedRng<Xorshift128PlusRng, WeylRng, CombineXor>),
+    #[cfg(feature = "xorshift")]
+    entry!("xorshift_32", 32, Xorshift32Rng),
+    #[cfg(feature = "xorshift")]
+    entry!("xorshift_64", 64, Xorshift64Rng),
+    #[cfg(all(feature = "xorshift", feature = "weyl"))]
+    entry!("xorshift_64_add_weyl", 64,
+           CombinedRng<Xorshift64Rng, WeylRng, CombineAdd>),
+    #[cfg(feature = "xorshift")]
+    entry!("xorshift_mt_32", 32, XorshiftMt32Rng),
+    #[cfg(feature = "xorshift")]
+    entry!("xorshift_mt_64", 64, XorshiftMt64Rng),
+    #[cfg(feature = "simd")]
+    entry!("xoshiro_256x4", 64, Xoshiro256x4Rng),
+    #[cfg(feature = "xsm")]
+    entry!("xsm32", 32, Xsm32Rng),
+    #[cfg(feature = "xsm")]
+    entry!("xsm64", 64, Xsm64Rng),
+];
+
+/// Look up a generator by name.
+pub fn find(name: &str) -> Option<&'static RngEntry> {
+    GENERATORS.iter().find(|entry| entry.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "msws")]
+    #[test]
+    fn msws_weak_seed() {
+        let entry = find("msws").unwrap();
+        let mut seed = [0x55; 16];
+        seed[4..8].copy_from_slice(&[0; 4]);
+        assert!(!entry.is_valid_seed(&seed));
+        assert!(entry.from_seed(&seed).is_none());
+        seed[7] = 1;
+        assert!(entry.from_seed(&seed).is_some());
+    }
+
+    #[test]
+    fn wrong_seed_len() {
+        for entry in GENERATORS.iter() {
+            let seed = [0x55; 100];
+            assert!(entry.from_seed(&seed[..entry.seed_len() + 1]).is_none());
+        }
+    }
+}
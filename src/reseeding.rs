@@ -0,0 +1,184 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reseeding after a number of output bytes.
+
+use rand_core::{RngCore, SeedableRng, Error};
+
+use crate::entropy::EntropySource;
+
+/// A wrapper that reseeds a generator from `source` after it produced
+/// `budget` bytes of output.
+///
+/// Seeding the generators in this crate costs no more than a few outputs, so
+/// reseeding often is cheap. The budget is checked before every call, so a
+/// single large `fill_bytes` can go over it.
+///
+/// If reseeding fails, the wrapper keeps using the current generator and tries
+/// again after another `budget` bytes. Only `reseed_now` returns the error.
+#[derive(Clone)]
+pub struct ReseedingSmallRng<R, S = EntropySource> {
+    rng: R,
+    source: S,
+    budget: u64,
+    remaining: u64,
+}
+
+/// The budget used by `from_seed` and `from_rng`: 1 MiB.
+pub const DEFAULT_RESEED_BUDGET: u64 = 1 << 20;
+
+impl<R: SeedableRng + RngCore, S: RngCore> ReseedingSmallRng<R, S> {
+    /// Wrap a generator, which is reseeded from `source` after every `budget`
+    /// bytes of output.
+    pub fn new(rng: R, source: S, budget: u64) -> Self {
+        assert!(budget > 0, "budget must be at least one byte");
+        Self { rng, source, budget, remaining: budget }
+    }
+
+    /// Reseed the generator from the source now, and reset the budget.
+    ///
+    /// If the source fails, the current generator is kept.
+    pub fn reseed_now(&mut self) -> Result<(), Error> {
+        self.remaining = self.budget;
+        self.rng = R::from_rng(&mut self.source)?;
+        Ok(())
+    }
+
+    /// Number of bytes that can be generated before the next reseed.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    #[inline]
+    fn spend(&mut self, bytes: usize) {
+        if self.remaining == 0 {
+            // On failure the budget is reset, and we retry after it is spent.
+            let _ = self.reseed_now();
+        }
+        self.remaining = self.remaining.saturating_sub(bytes as u64);
+    }
+}
+
+impl<R, S> SeedableRng for ReseedingSmallRng<R, S>
+    where R: SeedableRng + RngCore, S: RngCore + Default
+{
+    type Seed = R::Seed;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(R::from_seed(seed), S::default(), DEFAULT_RESEED_BUDGET)
+    }
+
+    fn from_rng<T: RngCore>(rng: T) -> Result<Self, Error> {
+        Ok(Self::new(R::from_rng(rng)?, S::default(), DEFAULT_RESEED_BUDGET))
+    }
+}
+
+impl<R: SeedableRng + RngCore, S: RngCore> RngCore for ReseedingSmallRng<R, S> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.spend(4);
+        self.rng.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.spend(8);
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.spend(dest.len());
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.spend(dest.len());
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroU32;
+    use rand_core::{RngCore, SeedableRng, Error};
+    use crate::mock::Words;
+    use super::*;
+
+    // Outputs its seed, so the tests can see when it is reseeded.
+    struct Fixed(u64);
+
+    impl SeedableRng for Fixed {
+        type Seed = [u8; 8];
+
+        fn from_seed(seed: Self::Seed) -> Self {
+            Fixed(u64::from_le_bytes(seed))
+        }
+    }
+
+    impl RngCore for Fixed {
+        fn next_u32(&mut self) -> u32 { self.0 as u32 }
+        fn next_u64(&mut self) -> u64 { self.0 }
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.copy_from_slice(&self.0.to_le_bytes()[..dest.len()])
+        }
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    const FAILURE: u32 = Error::CUSTOM_START + 1;
+
+    // A source that always fails.
+    struct Failing;
+
+    impl RngCore for Failing {
+        fn next_u32(&mut self) -> u32 { unimplemented!() }
+        fn next_u64(&mut self) -> u64 { unimplemented!() }
+        fn fill_bytes(&mut self, _dest: &mut [u8]) { unimplemented!() }
+        fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), Error> {
+            Err(Error::from(NonZeroU32::new(FAILURE).unwrap()))
+        }
+    }
+
+    #[test]
+    fn reseeds_when_budget_is_spent() {
+        let mut rng = ReseedingSmallRng::new(Fixed(0), Words(&[1, 2]), 12);
+        assert_eq!(rng.next_u64(), 0);
+        assert_eq!(rng.next_u32(), 0);
+        assert_eq!(rng.remaining(), 0);
+        assert_eq!(rng.next_u32(), 1);
+        assert_eq!(rng.remaining(), 8);
+        assert_eq!(rng.next_u64(), 1);
+        assert_eq!(rng.next_u64(), 2);
+    }
+
+    #[test]
+    fn reseed_now() {
+        let mut rng = ReseedingSmallRng::new(Fixed(0), Words(&[1]), 16);
+        assert_eq!(rng.next_u64(), 0);
+        rng.reseed_now().unwrap();
+        assert_eq!(rng.remaining(), 16);
+        assert_eq!(rng.next_u64(), 1);
+        assert_eq!(rng.remaining(), 8);
+    }
+
+    #[test]
+    fn failing_source_keeps_state() {
+        let mut rng = ReseedingSmallRng::new(Fixed(7), Failing, 8);
+        let err = rng.reseed_now().unwrap_err();
+        assert_eq!(err.code(), NonZeroU32::new(FAILURE));
+        assert_eq!(rng.next_u64(), 7);
+        // The automatic reseed fails silently, and resets the budget.
+        assert_eq!(rng.next_u64(), 7);
+        assert_eq!(rng.remaining(), 0);
+        assert_eq!(rng.next_u32(), 7);
+        assert_eq!(rng.remaining(), 4);
+    }
+}
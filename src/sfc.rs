@@ -11,6 +11,7 @@
 
 use rand_core::{RngCore, SeedableRng, Error, impls, le};
 use core::slice;
+use crate::cycles::CycleState;
 
 /// A Small Fast Counting RNG designed by Chris Doty-Humphrey (32-bit version).
 ///
@@ -30,6 +31,15 @@ pub struct Sfc32Rng {
     counter: u32,
 }
 
+impl Sfc32Rng {
+    /// Construct the generator from its state, with the counter at 1, which
+    /// can be done in a `static`. Unlike `from_seed` this does not skip the
+    /// first 15 outputs, so `a`, `b` and `c` should already be well mixed.
+    pub const fn new_unmixed(a: u32, b: u32, c: u32) -> Self {
+        Self { a, b, c, counter: 1 }
+    }
+}
+
 impl SeedableRng for Sfc32Rng {
     type Seed = [u8; 12];
 
@@ -114,6 +124,15 @@ pub struct Sfc64Rng {
     counter: u64,
 }
 
+impl Sfc64Rng {
+    /// Construct the generator from its state, with the counter at 1, which
+    /// can be done in a `static`. Unlike `from_seed` this does not skip the
+    /// first 18 outputs, so `a`, `b` and `c` should already be well mixed.
+    pub const fn new_unmixed(a: u64, b: u64, c: u64) -> Self {
+        Self { a, b, c, counter: 1 }
+    }
+}
+
 impl SeedableRng for Sfc64Rng {
     type Seed = [u8; 24];
 
@@ -175,3 +194,130 @@ impl RngCore for Sfc64Rng {
         Ok(self.fill_bytes(dest))
     }
 }
+
+
+
+
+
+macro_rules! sfc_small {
+    ($(#[$doc:meta])* $name:ident, $ty:ident, $bits:expr,
+     $barrel_shift:expr, $rshift:expr, $lshift:expr) => {
+        $(#[$doc])*
+        #[derive(Clone)]
+        pub struct $name {
+            a: $ty,
+            b: $ty,
+            c: $ty,
+            counter: $ty,
+        }
+
+        impl $name {
+            #[inline]
+            fn next_word(&mut self) -> $ty {
+                let tmp = self.a.wrapping_add(self.b).wrapping_add(self.counter);
+                self.counter = self.counter.wrapping_add(1);
+                self.a = self.b ^ (self.b >> $rshift);
+                self.b = self.c.wrapping_add(self.c << $lshift);
+                self.c = self.c.rotate_left($barrel_shift).wrapping_add(tmp);
+                tmp
+            }
+        }
+
+        impl SeedableRng for $name {
+            type Seed = [u8; 3 * $bits / 8];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                let mut seed_words = [0; 3];
+                for (word, bytes) in seed_words.iter_mut()
+                                               .zip(seed.chunks($bits / 8)) {
+                    let mut buf = [0u8; $bits / 8];
+                    buf.copy_from_slice(bytes);
+                    *word = $ty::from_le_bytes(buf);
+                }
+                let mut state = Self { a: seed_words[0],
+                                       b: seed_words[1],
+                                       c: seed_words[2],
+                                       counter: 1};
+                // Skip the first 15 outputs, just in case we have a bad seed.
+                for _ in 0..15 {
+                    state.next_word();
+                }
+                state
+            }
+        }
+
+        impl RngCore for $name {
+            #[inline]
+            fn next_u32(&mut self) -> u32 {
+                let mut x = 0;
+                for i in 0..(32 / $bits) {
+                    x |= (self.next_word() as u32) << (i * $bits);
+                }
+                x
+            }
+
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                impls::next_u64_via_u32(self)
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                impls::fill_bytes_via_next(self, dest)
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        impl CycleState for $name {
+            const STATE_BITS: u32 = 4 * $bits;
+
+            fn state(&self) -> u64 {
+                self.a as u64 | (self.b as u64) << $bits |
+                (self.c as u64) << (2 * $bits) |
+                (self.counter as u64) << (3 * $bits)
+            }
+
+            fn from_state(state: u64) -> Self {
+                Self { a: state as $ty,
+                       b: (state >> $bits) as $ty,
+                       c: (state >> (2 * $bits)) as $ty,
+                       counter: (state >> (3 * $bits)) as $ty }
+            }
+
+            fn step(&mut self) {
+                self.next_word();
+            }
+        }
+    }
+}
+
+sfc_small!(
+    /// A Small Fast Counting RNG scaled down to 8-bit words, to study the cycle
+    /// structure of SFC.
+    ///
+    /// - Author: Chris Doty-Humphrey
+    /// - License: Public domain
+    /// - Source: [PractRand](http://pracrand.sourceforge.net/)
+    /// - Period: min >= 2<sup>8</sup>, see `cycle_analysis`
+    /// - State: 32 bits
+    /// - Word size: 8 bits
+    /// - Seed size: 24 bits
+    /// - Low quality, for analysis only
+    Sfc8Rng, u8, 8, 3, 2, 1);
+
+sfc_small!(
+    /// A Small Fast Counting RNG scaled down to 16-bit words, to study the
+    /// cycle structure of SFC.
+    ///
+    /// - Author: Chris Doty-Humphrey
+    /// - License: Public domain
+    /// - Source: [PractRand](http://pracrand.sourceforge.net/)
+    /// - Period: min >= 2<sup>16</sup>, see `cycle_analysis`
+    /// - State: 64 bits
+    /// - Word size: 16 bits
+    /// - Seed size: 48 bits
+    /// - Low quality, for analysis only
+    Sfc16Rng, u16, 16, 6, 5, 3);
@@ -0,0 +1,142 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Bindings to the TestU01 test batteries.
+//!
+//! Requires the TestU01 libraries (`libtestu01`, `libprobdist` and `libmylib`)
+//! to be installed where the linker can find them.
+
+use rand_core::RngCore;
+use std::boxed::Box;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr;
+use std::string::String;
+use std::thread_local;
+use std::vec::Vec;
+
+/// Opaque `unif01_Gen` of TestU01.
+#[repr(C)]
+struct Unif01Gen {
+    _private: [u8; 0],
+}
+
+// Size of the result arrays in `bbattery.c`.
+const NDIM: usize = 200;
+
+#[link(name = "testu01")]
+#[link(name = "probdist")]
+#[link(name = "mylib")]
+extern "C" {
+    fn unif01_CreateExternGenBits(name: *mut c_char,
+                                  gen_b: extern "C" fn() -> c_uint)
+                                  -> *mut Unif01Gen;
+    fn unif01_DeleteExternGenBits(gen: *mut Unif01Gen);
+
+    fn bbattery_SmallCrush(gen: *mut Unif01Gen);
+    fn bbattery_Crush(gen: *mut Unif01Gen);
+    fn bbattery_BigCrush(gen: *mut Unif01Gen);
+
+    static bbattery_NTests: c_int;
+    static bbattery_pVal: [f64; 1 + NDIM];
+    static bbattery_TestNames: [*const c_char; 1 + NDIM];
+}
+
+extern "C" {
+    fn fflush(stream: *mut c_void) -> c_int;
+}
+
+thread_local! {
+    // `unif01_CreateExternGenBits` takes a plain function without context, so
+    // the generator under test has to be global.
+    static GENERATOR: RefCell<Option<Box<dyn RngCore>>> = RefCell::new(None);
+}
+
+extern "C" fn next_bits() -> c_uint {
+    GENERATOR.with(|rng| {
+        rng.borrow_mut().as_mut().expect("no generator under test").next_u32()
+    })
+}
+
+/// A TestU01 test battery.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Battery {
+    /// 15 tests, takes seconds.
+    SmallCrush,
+    /// 144 tests, takes about an hour.
+    Crush,
+    /// 160 tests, takes several hours.
+    BigCrush,
+}
+
+/// The p-values of all tests in a battery.
+#[derive(Clone, Debug)]
+pub struct Summary {
+    pub battery: Battery,
+    /// Name and p-value of every test.
+    pub results: Vec<(String, f64)>,
+}
+
+impl Summary {
+    /// Tests with a p-value outside `[threshold, 1 - threshold]`.
+    pub fn outside(&self, threshold: f64) -> Vec<&(String, f64)> {
+        self.results.iter()
+            .filter(|&&(_, p)| p < threshold || p > 1.0 - threshold)
+            .collect()
+    }
+
+    /// Tests that TestU01 reports as suspicious (p outside `[0.001, 0.999]`).
+    pub fn suspicious(&self) -> Vec<&(String, f64)> {
+        self.outside(0.001)
+    }
+
+    /// Tests that are clear failures (p outside `[1e-10, 1 - 1e-10]`).
+    pub fn failures(&self) -> Vec<&(String, f64)> {
+        self.outside(1.0e-10)
+    }
+}
+
+/// Run a test battery on the 32-bit output of `rng`.
+///
+/// TestU01 prints its own report to stdout while running.
+pub fn run_battery(rng: Box<dyn RngCore>, name: &str, battery: Battery)
+    -> Summary
+{
+    GENERATOR.with(|gen| *gen.borrow_mut() = Some(rng));
+    let name = CString::new(name).expect("name contains a nul byte");
+
+    let mut results = Vec::new();
+    unsafe {
+        let gen = unif01_CreateExternGenBits(name.as_ptr() as *mut c_char,
+                                             next_bits);
+        match battery {
+            Battery::SmallCrush => bbattery_SmallCrush(gen),
+            Battery::Crush => bbattery_Crush(gen),
+            Battery::BigCrush => bbattery_BigCrush(gen),
+        }
+        unif01_DeleteExternGenBits(gen);
+        // Make sure the report of TestU01 comes before anything we print.
+        fflush(ptr::null_mut());
+
+        let n_tests = (bbattery_NTests.max(0) as usize).min(NDIM);
+        for i in 0..n_tests {
+            let test_name = bbattery_TestNames[i];
+            let test_name = if test_name.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(test_name).to_string_lossy().into_owned()
+            };
+            results.push((test_name, bbattery_pVal[i]));
+        }
+    }
+
+    GENERATOR.with(|gen| *gen.borrow_mut() = None);
+    Summary { battery, results }
+}
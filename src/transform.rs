@@ -0,0 +1,173 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Transformations of the output of a generator, for testing.
+
+use rand_core::{RngCore, Error};
+
+/// Transformations applied to every output word, in the order of the fields.
+///
+/// Most statistical tests are more sensitive to the high bits of a word, or to
+/// the first bytes of the stream. PractRand recommends to also test a generator
+/// with reversed output, to expose weaknesses in the other end of each word.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Transform {
+    /// Reverse the order of the bytes in each word.
+    pub byte_swap: bool,
+    /// Reverse the order of the bits in each word.
+    pub bit_reverse: bool,
+    /// Invert all bits.
+    pub complement: bool,
+}
+
+impl Transform {
+    #[inline]
+    fn apply_u32(&self, mut x: u32) -> u32 {
+        if self.byte_swap { x = x.swap_bytes(); }
+        if self.bit_reverse { x = x.reverse_bits(); }
+        if self.complement { x = !x; }
+        x
+    }
+
+    #[inline]
+    fn apply_u64(&self, mut x: u64) -> u64 {
+        if self.byte_swap { x = x.swap_bytes(); }
+        if self.bit_reverse { x = x.reverse_bits(); }
+        if self.complement { x = !x; }
+        x
+    }
+}
+
+/// A wrapper that transforms every output word of a generator.
+///
+/// `next_u32` and `next_u64` transform the word of that size. `fill_bytes`
+/// fills the buffer with transformed words of `word_bits` bits, which should
+/// match the word size of the wrapped generator. It does not use the
+/// `fill_bytes` method of the wrapped generator.
+#[derive(Clone, Debug)]
+pub struct TransformRng<R> {
+    rng: R,
+    transform: Transform,
+    word_bits: u32,
+}
+
+impl<R: RngCore> TransformRng<R> {
+    /// Wrap a generator, with words of 64 bits for `fill_bytes`.
+    pub fn new(rng: R, transform: Transform) -> Self {
+        Self { rng, transform, word_bits: 64 }
+    }
+
+    /// Set the word size used by `fill_bytes`, 32 or 64 bits.
+    pub fn with_word_bits(mut self, word_bits: u32) -> Self {
+        assert!(word_bits == 32 || word_bits == 64,
+                "word size must be 32 or 64 bits");
+        self.word_bits = word_bits;
+        self
+    }
+
+    /// Return the wrapped generator.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+}
+
+impl<R: RngCore> RngCore for TransformRng<R> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.transform.apply_u32(self.rng.next_u32())
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.transform.apply_u64(self.rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        // Not `impls::fill_bytes_via_next`, which uses `next_u32` for the last
+        // bytes.
+        if self.word_bits == 64 {
+            for chunk in dest.chunks_mut(8) {
+                let bytes = self.next_u64().to_le_bytes();
+                chunk.copy_from_slice(&bytes[..chunk.len()]);
+            }
+        } else {
+            for chunk in dest.chunks_mut(4) {
+                let bytes = self.next_u32().to_le_bytes();
+                chunk.copy_from_slice(&bytes[..chunk.len()]);
+            }
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::RngCore;
+    use crate::mock::Words;
+    use super::*;
+
+    const WORDS: [u64; 2] = [0x0123_4567_89ab_cdef, 0x0f1e_2d3c_4b5a_6978];
+
+    fn transforms() -> impl Iterator<Item = Transform> {
+        (0..8).map(|i| Transform {
+            byte_swap: i & 1 != 0,
+            bit_reverse: i & 2 != 0,
+            complement: i & 4 != 0,
+        })
+    }
+
+    #[test]
+    fn transform_words() {
+        let t = |byte_swap, bit_reverse, complement| {
+            let transform = Transform { byte_swap, bit_reverse, complement };
+            TransformRng::new(Words(&WORDS), transform)
+        };
+        assert_eq!(t(false, false, false).next_u64(), 0x0123_4567_89ab_cdef);
+        assert_eq!(t(true, false, false).next_u64(), 0xefcd_ab89_6745_2301);
+        assert_eq!(t(false, true, false).next_u64(), 0xf7b3_d591_e6a2_c480);
+        assert_eq!(t(false, false, true).next_u64(), 0xfedc_ba98_7654_3210);
+        assert_eq!(t(true, true, true).next_u64(), 0x7f3b_5d19_6e2a_4c08);
+        assert_eq!(t(false, false, false).next_u32(), 0x89ab_cdef);
+        assert_eq!(t(true, false, false).next_u32(), 0xefcd_ab89);
+        assert_eq!(t(false, true, false).next_u32(), 0xf7b3_d591);
+        assert_eq!(t(false, false, true).next_u32(), 0x7654_3210);
+        assert_eq!(t(true, true, true).next_u32(), 0x6e2a_4c08);
+    }
+
+    // `fill_bytes` gives the little-endian bytes of the transformed words,
+    // also for the last, partial word.
+    #[test]
+    fn fill_bytes_matches_next() {
+        for transform in transforms() {
+            let mut rng = TransformRng::new(Words(&WORDS), transform);
+            let mut expected = [0u8; 16];
+            expected[..8].copy_from_slice(&rng.next_u64().to_le_bytes());
+            expected[8..].copy_from_slice(&rng.next_u64().to_le_bytes());
+            let mut rng = TransformRng::new(Words(&WORDS), transform);
+            let mut bytes = [0u8; 12];
+            rng.fill_bytes(&mut bytes);
+            assert_eq!(bytes, expected[..12], "{:?}", transform);
+
+            let mut rng = TransformRng::new(Words(&WORDS), transform)
+                .with_word_bits(32);
+            let mut expected = [0u8; 8];
+            expected[..4].copy_from_slice(&rng.next_u32().to_le_bytes());
+            expected[4..].copy_from_slice(&rng.next_u32().to_le_bytes());
+            let mut rng = TransformRng::new(Words(&WORDS), transform)
+                .with_word_bits(32);
+            let mut bytes = [0u8; 7];
+            rng.fill_bytes(&mut bytes);
+            assert_eq!(bytes, expected[..7], "{:?}", transform);
+        }
+    }
+}
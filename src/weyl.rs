@@ -0,0 +1,62 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A plain Weyl sequence.
+
+use rand_core::{RngCore, SeedableRng, Error, impls, le};
+
+/// A Weyl sequence: a counter with an odd increment.
+///
+/// - Author: Hermann Weyl
+/// - License: Public domain
+/// - Period: 2<sup>64</sup>
+/// - State: 128 bits
+/// - Word size: 64 bits
+/// - Seed size: 128 bits
+/// - Very low quality, only useful as a component of a combined generator.
+///   `next_u32` returns the high half of the word, because the low bits have
+///   a short period.
+#[derive(Clone)]
+pub struct WeylRng {
+    x: u64,
+    increment: u64,
+}
+
+impl SeedableRng for WeylRng {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut seed_u64 = [0u64; 2];
+        le::read_u64_into(&seed, &mut seed_u64);
+        // The increment must be odd.
+        Self { x: seed_u64[0], increment: seed_u64[1] | 1 }
+    }
+}
+
+impl RngCore for WeylRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.x = self.x.wrapping_add(self.increment);
+        self.x
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright 2017 Paul Dicker.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Widening multiplication.
+
+pub(crate) trait WideningMultiply<RHS = Self> {
+    type Output;
+
+    fn wmul(self, x: RHS) -> Self::Output;
+}
+
+macro_rules! wmul_impl {
+    ($ty:ty, $wide:ident, $shift:expr) => {
+        impl WideningMultiply for $ty {
+            type Output = ($ty, $ty);
+
+            #[inline(always)]
+            fn wmul(self, x: $ty) -> Self::Output {
+                let tmp = (self as $wide) * (x as $wide);
+                ((tmp >> $shift) as $ty, tmp as $ty)
+            }
+        }
+    }
+}
+
+wmul_impl! { u32, u64, 32 }
+#[cfg(not(target_pointer_width = "32"))]
+wmul_impl! { u64, u128, 64 }
+
+#[cfg(target_pointer_width = "32")]
+impl WideningMultiply for u64 {
+    type Output = (u64, u64);
+
+    #[inline(always)]
+    fn wmul(self, x: u64) -> Self::Output {
+        wmul_u64_portable(self, x)
+    }
+}
+
+// This code is a translation of the __mulddi3 function in LLVM's
+// compiler-rt. It is an optimised variant of the common method
+// `(a + b) * (c + d) = ac + ad + bc + bd`.
+//
+// For some reason LLVM can optimise the C version very well, but keeps
+// shuffeling registers in this Rust translation.
+#[cfg(any(target_pointer_width = "32", test))]
+#[inline(always)]
+fn wmul_u64_portable(a: u64, b: u64) -> (u64, u64) {
+    const LOWER_MASK: u64 = !0u64 >> 32;
+    let mut low = (a & LOWER_MASK).wrapping_mul(b & LOWER_MASK);
+    let mut t = low >> 32;
+    low &= LOWER_MASK;
+    t += (a >> 32).wrapping_mul(b & LOWER_MASK);
+    low += (t & LOWER_MASK) << 32;
+    let mut high = (t >> 32) as i64;
+    t = low >> 32;
+    low &= LOWER_MASK;
+    t += (b >> 32).wrapping_mul(a & LOWER_MASK);
+    low += (t & LOWER_MASK) << 32;
+    high += (t >> 32) as i64;
+    high += (a >> 32).wrapping_mul(b >> 32) as i64;
+
+    (high as u64, low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portable_u64() {
+        let values = [0, 1, 2, 0xffff_ffff, 0x1_0000_0000, 0xdead_beef_0bad_5eed,
+                      0x8000_0000_0000_0000, 2685821657736338717, !0 - 1, !0];
+        for &a in values.iter() {
+            for &b in values.iter() {
+                let wide = u128::from(a) * u128::from(b);
+                assert_eq!(wmul_u64_portable(a, b),
+                           ((wide >> 64) as u64, wide as u64));
+            }
+        }
+    }
+
+    #[test]
+    fn portable_u64_random() {
+        let mut x = 0u64;
+        for _ in 0..10_000 {
+            // Weyl sequence with a multiply, enough to cover all bits.
+            x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let a = x.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            let b = (a ^ (a >> 31)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            let wide = u128::from(a) * u128::from(b);
+            assert_eq!(wmul_u64_portable(a, b),
+                       ((wide >> 64) as u64, wide as u64));
+        }
+    }
+}
@@ -7,7 +7,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-//! Xoroshiro+ random number generators
+//! Xoroshiro random number generators
 
 use rand_core::{RngCore, SeedableRng, Error, impls, le};
 
@@ -70,6 +70,131 @@ impl RngCore for Xoroshiro128PlusRng {
 }
 
 
+/// The Xoroshiro128** random number generator.
+///
+/// Uses different shift and rotation constants than Xoroshiro128+, and a
+/// multiply-rotate-multiply scrambler, so all output bits pass linearity tests.
+///
+/// - Author: David Blackman and Sebastiano Vigna
+/// - License: Public domain
+/// - Source: [xoroshiro128starstar.c](https://prng.di.unimi.it/xoroshiro128starstar.c)
+/// - Period: 2<sup>128</sup> - 1
+/// - State: 128 bits
+/// - Word size: 64 bits
+/// - Seed size: 128 bits
+#[derive(Clone)]
+pub struct Xoroshiro128StarStarRng {
+    s0: u64,
+    s1: u64,
+}
+
+impl SeedableRng for Xoroshiro128StarStarRng {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut seed_u64 = [0u64; 2];
+        le::read_u64_into(&seed, &mut seed_u64);
+
+        if seed_u64.iter().all(|&x| x == 0) {
+            seed_u64 = [0x0DD_B1A5E5_BAD_5EED, 0x0DD_B1A5E5_BAD_5EED];
+        }
+
+        Self { s0: seed_u64[0], s1: seed_u64[1] }
+    }
+}
+
+impl RngCore for Xoroshiro128StarStarRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let s0 = self.s0;
+        let mut s1 = self.s1;
+        let result = s0.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+
+        s1 ^= s0;
+        self.s0 = s0.rotate_left(24) ^ s1 ^ (s1 << 16); // a, b
+        self.s1 = s1.rotate_left(37); // c
+
+        result
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+
+/// The Xoroshiro128++ random number generator.
+///
+/// Uses different shift and rotation constants than Xoroshiro128+, and an
+/// add-rotate-add scrambler, so all output bits pass linearity tests.
+///
+/// - Author: David Blackman and Sebastiano Vigna
+/// - License: Public domain
+/// - Source: [xoroshiro128plusplus.c](https://prng.di.unimi.it/xoroshiro128plusplus.c)
+/// - Period: 2<sup>128</sup> - 1
+/// - State: 128 bits
+/// - Word size: 64 bits
+/// - Seed size: 128 bits
+#[derive(Clone)]
+pub struct Xoroshiro128PlusPlusRng {
+    s0: u64,
+    s1: u64,
+}
+
+impl SeedableRng for Xoroshiro128PlusPlusRng {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut seed_u64 = [0u64; 2];
+        le::read_u64_into(&seed, &mut seed_u64);
+
+        if seed_u64.iter().all(|&x| x == 0) {
+            seed_u64 = [0x0DD_B1A5E5_BAD_5EED, 0x0DD_B1A5E5_BAD_5EED];
+        }
+
+        Self { s0: seed_u64[0], s1: seed_u64[1] }
+    }
+}
+
+impl RngCore for Xoroshiro128PlusPlusRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let s0 = self.s0;
+        let mut s1 = self.s1;
+        let result = s0.wrapping_add(s1).rotate_left(17).wrapping_add(s0);
+
+        s1 ^= s0;
+        self.s0 = s0.rotate_left(49) ^ s1 ^ (s1 << 21); // a, b
+        self.s1 = s1.rotate_left(28); // c
+
+        result
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
 /// A 32-bit variant of Xoroshiro128+, with just 64 bits of state.
 #[derive(Clone)]
 pub struct Xoroshiro64PlusRng {
@@ -119,3 +244,131 @@ impl RngCore for Xoroshiro64PlusRng {
         Ok(self.fill_bytes(dest))
     }
 }
+
+
+/// The Xoroshiro64** random number generator.
+///
+/// - Author: David Blackman and Sebastiano Vigna
+/// - License: Public domain
+/// - Source: [xoroshiro64starstar.c](https://prng.di.unimi.it/xoroshiro64starstar.c)
+/// - Period: 2<sup>64</sup> - 1
+/// - State: 64 bits
+/// - Word size: 32 bits
+/// - Seed size: 64 bits
+#[derive(Clone)]
+pub struct Xoroshiro64StarStarRng {
+    s0: u32,
+    s1: u32,
+}
+
+impl SeedableRng for Xoroshiro64StarStarRng {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut seed_u32 = [0u32; 2];
+        le::read_u32_into(&seed, &mut seed_u32);
+
+        if seed_u32.iter().all(|&x| x == 0) {
+            seed_u32 = [0xBAD_5EED, 0xBAD_5EED];
+        }
+
+        Self { s0: seed_u32[0], s1: seed_u32[1] }
+    }
+}
+
+impl RngCore for Xoroshiro64StarStarRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let s0 = self.s0;
+        let mut s1 = self.s1;
+        let result = s0.wrapping_mul(0x9E3779BB).rotate_left(5).wrapping_mul(5);
+
+        s1 ^= s0;
+        self.s0 = s0.rotate_left(26) ^ s1 ^ (s1 << 9); // a, b
+        self.s1 = s1.rotate_left(13); // c
+
+        result
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_u32(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::{RngCore, SeedableRng};
+    use super::*;
+
+    // The state used for the reference outputs, and the seed giving it.
+    const S128: [u64; 2] = [0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210];
+    const S64: [u32; 2] = [0x0123_4567, 0x89ab_cdef];
+
+    fn seed128() -> [u8; 16] {
+        let mut seed = [0u8; 16];
+        seed[..8].copy_from_slice(&S128[0].to_le_bytes());
+        seed[8..].copy_from_slice(&S128[1].to_le_bytes());
+        seed
+    }
+
+    fn seed64() -> [u8; 8] {
+        let mut seed = [0u8; 8];
+        seed[..4].copy_from_slice(&S64[0].to_le_bytes());
+        seed[4..].copy_from_slice(&S64[1].to_le_bytes());
+        seed
+    }
+
+    // Outputs of `xoroshiro128starstar.c` by Blackman and Vigna.
+    #[test]
+    fn xoroshiro128starstar_reference() {
+        let mut rng = Xoroshiro128StarStarRng::from_seed(seed128());
+        let expected = [11068046444225724818, 11068046341419981074,
+                        10201203040204483364, 11160412049735024024];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u64(), x);
+        }
+        for _ in expected.len()..999 {
+            rng.next_u64();
+        }
+        assert_eq!(rng.next_u64(), 13132937305218649252);
+    }
+
+    // Outputs of `xoroshiro128plusplus.c` by Blackman and Vigna.
+    #[test]
+    fn xoroshiro128plusplus_reference() {
+        let mut rng = Xoroshiro128PlusPlusRng::from_seed(seed128());
+        let expected = [81985529216486894, 11559359155456189540,
+                        14742413462175304280, 11722321085874246307];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u64(), x);
+        }
+        for _ in expected.len()..999 {
+            rng.next_u64();
+        }
+        assert_eq!(rng.next_u64(), 13662253521581016920);
+    }
+
+    // Outputs of `xoroshiro64starstar.c` by Blackman and Vigna.
+    #[test]
+    fn xoroshiro64starstar_reference() {
+        let mut rng = Xoroshiro64StarStarRng::from_seed(seed64());
+        let expected = [1333577403, 1319054828, 3953444674, 426888066];
+        for &x in expected.iter() {
+            assert_eq!(rng.next_u32(), x);
+        }
+        for _ in expected.len()..999 {
+            rng.next_u32();
+        }
+        assert_eq!(rng.next_u32(), 685864026);
+    }
+}
@@ -11,6 +11,9 @@
 
 use rand_core::{RngCore, SeedableRng, Error, impls, le};
 
+#[cfg(target_pointer_width = "32")]
+use crate::wmul::WideningMultiply;
+
 #[derive(Clone)]
 pub struct XoroshiroMt32of128Rng {
     s0: u64,
@@ -131,59 +134,3 @@ impl RngCore for XoroshiroMt64of128Rng {
         Ok(self.fill_bytes(dest))
     }
 }
-
-
-
-trait WideningMultiply<RHS = Self> {
-    type Output;
-
-    fn wmul(self, x: RHS) -> Self::Output;
-}
-
-macro_rules! wmul_impl {
-    ($ty:ty, $wide:ident, $shift:expr) => {
-        impl WideningMultiply for $ty {
-            type Output = ($ty, $ty);
-
-            #[inline(always)]
-            fn wmul(self, x: $ty) -> Self::Output {
-                let tmp = (self as $wide) * (x as $wide);
-                ((tmp >> $shift) as $ty, tmp as $ty)
-            }
-        }
-    }
-}
-
-wmul_impl! { u32, u64, 32 }
-#[cfg(not(any(target_pointer_width = "32")))]
-wmul_impl! { u64, u128, 64 }
-
-#[cfg(any(target_pointer_width = "32"))]
-impl WideningMultiply for u64 {
-    type Output = (u64, u64);
-
-    // This code is a translation of the __mulddi3 function in LLVM's
-    // compiler-rt. It is an optimised variant of the common method
-    // `(a + b) * (c + d) = ac + ad + bc + bd`.
-    //
-    // For some reason LLVM can optimise the C version very well, but keeps
-    // shuffeling registers in this Rust translation.
-    #[inline(always)]
-    fn wmul(self, b: u64) -> Self::Output {
-        const LOWER_MASK: u64 = !0u64 >> 32;
-        let mut low = (self & LOWER_MASK).wrapping_mul(b & LOWER_MASK);
-        let mut t = low >> 32;
-        low &= LOWER_MASK;
-        t += (self >> 32).wrapping_mul(b & LOWER_MASK);
-        low += (t & LOWER_MASK) << 32;
-        let mut high = (t >> 32) as i64;
-        t = low >> 32;
-        low &= LOWER_MASK;
-        t += (b >> 32).wrapping_mul(self & LOWER_MASK);
-        low += (t & LOWER_MASK) << 32;
-        high += (t >> 32) as i64;
-        high += (self >> 32).wrapping_mul(b >> 32) as i64;
-
-        (high as u64, low)
-    }
-}
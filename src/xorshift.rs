@@ -8,41 +8,262 @@
 // except according to those terms.
 
 //! Plain Xorshift rondom number generators
+//!
+//! The engines are generic over the shift triple `(A, B, C)`, so any of the
+//! published parameter sets can be used. All shifts must be in the range
+//! `1..word size`, which is checked at compile time, and only a few triples
+//! give the full period.
 
 use rand_core::{RngCore, SeedableRng, Error, impls, le};
 use core::fmt;
 
-/// An Xorshift random number generator (128/32-bit variant).
+const fn valid_shifts(a: u32, b: u32, c: u32, bits: u32) -> bool {
+    a >= 1 && a < bits && b >= 1 && b < bits && c >= 1 && c < bits
+}
+
+/// An Xorshift random number generator with 32 bits of state.
 ///
 /// - Author: George Marsaglia
 /// - License: Public domain
 /// - Source: ["Xorshift RNGs"](http://www.jstatsoft.org/v08/i14/paper).
 ///           *Journal of Statistical Software*. Vol. 8 (Issue 14).
-/// - Period: 2<sup>128</sup> - 1
+/// - Period: 2<sup>32</sup> - 1 (for a full-period shift triple)
+/// - State: 32 bits
+/// - Word size: 32 bits
+/// - Seed size: 32 bits
+/// - Low quality
+#[derive(Clone)]
+pub struct Xorshift32Engine<const A: u32, const B: u32, const C: u32> {
+    x: u32,
+}
+
+/// Marsaglia's 32-bit xorshift with the shift triple (13, 17, 5).
+pub type Xorshift32Rng = Xorshift32Engine<13, 17, 5>;
+
+impl<const A: u32, const B: u32, const C: u32> Xorshift32Engine<A, B, C> {
+    // Referenced by the constructors, so an invalid shift triple fails to
+    // compile.
+    const VALID_SHIFTS: () = assert!(valid_shifts(A, B, C, 32),
+                                     "shifts must be in the range 1..32");
+
+    /// Construct the generator from its state, which can be done in a
+    /// `static`. A zero state is replaced, like in `from_seed`.
+    pub const fn new_unmixed(x: u32) -> Self {
+        let () = Self::VALID_SHIFTS;
+        Self { x: if x == 0 { 0xBAD_5EED } else { x } }
+    }
+}
+
+impl<const A: u32, const B: u32, const C: u32> fmt::Debug
+    for Xorshift32Engine<A, B, C>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Xorshift32Engine<{}, {}, {}> {{}}", A, B, C)
+    }
+}
+
+impl<const A: u32, const B: u32, const C: u32> SeedableRng
+    for Xorshift32Engine<A, B, C>
+{
+    type Seed = [u8; 4];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let () = Self::VALID_SHIFTS;
+        let mut seed_u32 = [0u32; 1];
+        le::read_u32_into(&seed, &mut seed_u32);
+
+        if seed_u32[0] == 0 {
+            seed_u32[0] = 0xBAD_5EED;
+        }
+
+        Self { x: seed_u32[0] }
+    }
+}
+
+impl<const A: u32, const B: u32, const C: u32> RngCore
+    for Xorshift32Engine<A, B, C>
+{
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let mut x = self.x;
+        x ^= x << A;
+        x ^= x >> B;
+        x ^= x << C;
+        self.x = x;
+        x
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_u32(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// An Xorshift random number generator with 64 bits of state.
+///
+/// - Author: George Marsaglia
+/// - License: Public domain
+/// - Source: ["Xorshift RNGs"](http://www.jstatsoft.org/v08/i14/paper).
+///           *Journal of Statistical Software*. Vol. 8 (Issue 14).
+/// - Period: 2<sup>64</sup> - 1 (for a full-period shift triple)
+/// - State: 64 bits
+/// - Word size: 64 bits
+/// - Seed size: 64 bits
+/// - Low quality
+#[derive(Clone)]
+pub struct Xorshift64Engine<const A: u32, const B: u32, const C: u32> {
+    x: u64,
+}
+
+/// Marsaglia's 64-bit xorshift with the shift triple (13, 7, 17).
+pub type Xorshift64Rng = Xorshift64Engine<13, 7, 17>;
+
+impl<const A: u32, const B: u32, const C: u32> Xorshift64Engine<A, B, C> {
+    // Referenced by the constructors, so an invalid shift triple fails to
+    // compile.
+    const VALID_SHIFTS: () = assert!(valid_shifts(A, B, C, 64),
+                                     "shifts must be in the range 1..64");
+
+    /// Construct the generator from its state, which can be done in a
+    /// `static`. A zero state is replaced, like in `from_seed`.
+    pub const fn new_unmixed(x: u64) -> Self {
+        let () = Self::VALID_SHIFTS;
+        Self { x: if x == 0 { 0x0DD_B1A5E5_BAD_5EED } else { x } }
+    }
+}
+
+impl<const A: u32, const B: u32, const C: u32> fmt::Debug
+    for Xorshift64Engine<A, B, C>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Xorshift64Engine<{}, {}, {}> {{}}", A, B, C)
+    }
+}
+
+impl<const A: u32, const B: u32, const C: u32> SeedableRng
+    for Xorshift64Engine<A, B, C>
+{
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let () = Self::VALID_SHIFTS;
+        let mut seed_u64 = [0u64; 1];
+        le::read_u64_into(&seed, &mut seed_u64);
+
+        if seed_u64[0] == 0 {
+            seed_u64[0] = 0x0DD_B1A5E5_BAD_5EED;
+        }
+
+        Self { x: seed_u64[0] }
+    }
+}
+
+impl<const A: u32, const B: u32, const C: u32> RngCore
+    for Xorshift64Engine<A, B, C>
+{
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.x;
+        x ^= x << A;
+        x ^= x >> B;
+        x ^= x << C;
+        self.x = x;
+        x
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// An Xorshift random number generator with 128 bits of state, in four 32-bit
+/// words.
+///
+/// - Author: George Marsaglia
+/// - License: Public domain
+/// - Source: ["Xorshift RNGs"](http://www.jstatsoft.org/v08/i14/paper).
+///           *Journal of Statistical Software*. Vol. 8 (Issue 14).
+/// - Period: 2<sup>128</sup> - 1 (for a full-period shift triple)
 /// - State: 128 bits
 /// - Word size: 32 bits
 /// - Seed size: 128 bits
 /// - Low quality
-/// - The small RNG currently available in rand (0.3.18)
 #[derive(Clone)]
 #[allow(non_camel_case_types)]
-pub struct Xorshift128_32Rng {
+pub struct Xorshift128_32Engine<const A: u32, const B: u32, const C: u32> {
     x: u32,
     y: u32,
     z: u32,
     w: u32,
 }
 
-impl fmt::Debug for Xorshift128_32Rng {
+/// An Xorshift random number generator (128/32-bit variant).
+///
+/// - Author: George Marsaglia
+/// - License: Public domain
+/// - Source: ["Xorshift RNGs"](http://www.jstatsoft.org/v08/i14/paper).
+///           *Journal of Statistical Software*. Vol. 8 (Issue 14).
+/// - Period: 2<sup>128</sup> - 1
+/// - State: 128 bits
+/// - Word size: 32 bits
+/// - Seed size: 128 bits
+/// - Low quality
+/// - The small RNG currently available in rand (0.3.18)
+#[allow(non_camel_case_types)]
+pub type Xorshift128_32Rng = Xorshift128_32Engine<11, 8, 19>;
+
+impl<const A: u32, const B: u32, const C: u32> Xorshift128_32Engine<A, B, C> {
+    // Referenced by the constructors, so an invalid shift triple fails to
+    // compile.
+    const VALID_SHIFTS: () = assert!(valid_shifts(A, B, C, 32),
+                                     "shifts must be in the range 1..32");
+
+    /// Construct the generator from its state, which can be done in a
+    /// `static`. An all-zero state is replaced, like in `from_seed`.
+    pub const fn new_unmixed(x: u32, y: u32, z: u32, w: u32) -> Self {
+        let () = Self::VALID_SHIFTS;
+        if x == 0 && y == 0 && z == 0 && w == 0 {
+            Self { x: 0xBAD_5EED, y: 0xBAD_5EED, z: 0xBAD_5EED, w: 0xBAD_5EED }
+        } else {
+            Self { x, y, z, w }
+        }
+    }
+}
+
+impl<const A: u32, const B: u32, const C: u32> fmt::Debug
+    for Xorshift128_32Engine<A, B, C>
+{
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Xorshift128_32Rng {{}}")
+        write!(f, "Xorshift128_32Engine<{}, {}, {}> {{}}", A, B, C)
     }
 }
 
-impl SeedableRng for Xorshift128_32Rng {
+impl<const A: u32, const B: u32, const C: u32> SeedableRng
+    for Xorshift128_32Engine<A, B, C>
+{
     type Seed = [u8; 16];
 
     fn from_seed(seed: Self::Seed) -> Self {
+        let () = Self::VALID_SHIFTS;
         let mut seed_u32 = [0u32; 4];
         le::read_u32_into(&seed, &mut seed_u32);
 
@@ -59,34 +280,36 @@ impl SeedableRng for Xorshift128_32Rng {
     }
 }
 
-impl RngCore for Xorshift128_32Rng {
+impl<const A: u32, const B: u32, const C: u32> RngCore
+    for Xorshift128_32Engine<A, B, C>
+{
     #[inline]
     fn next_u32(&mut self) -> u32 {
 /*
         // optimized version
         let x = self.x;
         // already do the first xorshift step of the next round
-        self.x = self.y ^ (self.y << 11);
+        self.x = self.y ^ (self.y << A);
         self.y = self.z;
         self.z = self.z;
         let t = self.w;
-        self.w = (x ^ (x >> 19)) ^ t ^ (t >> 8);
+        self.w = (x ^ (x >> C)) ^ t ^ (t >> B);
         self.w
 */
         let x = self.x;
-        let t = x ^ (x << 11);
+        let t = x ^ (x << A);
         self.x = self.y;
         self.y = self.z;
         self.z = self.w;
         let w = self.w;
-        self.w = w ^ (w >> 19) ^ (t ^ (t >> 8));
+        self.w = w ^ (w >> C) ^ (t ^ (t >> B));
         self.w
     }
-    
+
     fn next_u64(&mut self) -> u64 {
         impls::next_u64_via_u32(self)
     }
-    
+
     fn fill_bytes(&mut self, dest: &mut [u8]) {
         impls::fill_bytes_via_next(self, dest);
     }
@@ -96,6 +319,25 @@ impl RngCore for Xorshift128_32Rng {
     }
 }
 
+/// An Xorshift random number generator with 128 bits of state, in two 64-bit
+/// words.
+///
+/// - Author: Sebastiano Vigna
+/// - License: Public domain
+/// - Source: ["An experimental exploration of Marsaglia's xorshift
+///           generators, scrambled"](http://vigna.di.unimi.it/ftp/papers/xorshift.pdf)
+/// - Period: 2<sup>128</sup> - 1 (for a full-period shift triple)
+/// - State: 128 bits
+/// - Word size: 64 bits
+/// - Seed size: 128 bits
+/// - Low quality, very fast
+#[derive(Clone)]
+#[allow(non_camel_case_types)]
+pub struct Xorshift128_64Engine<const A: u32, const B: u32, const C: u32> {
+    s0: u64,
+    s1: u64,
+}
+
 /// An Xorshift random number generator (128/64-bit variant).
 ///
 /// - Author: George Marsaglia
@@ -107,23 +349,42 @@ impl RngCore for Xorshift128_32Rng {
 /// - Word size: 64 bits
 /// - Seed size: 128 bits
 /// - Low quality, very fast
-#[derive(Clone)]
 #[allow(non_camel_case_types)]
-pub struct Xorshift128_64Rng {
-    s0: u64,
-    s1: u64,
+pub type Xorshift128_64Rng = Xorshift128_64Engine<23, 18, 5>;
+
+impl<const A: u32, const B: u32, const C: u32> Xorshift128_64Engine<A, B, C> {
+    // Referenced by the constructors, so an invalid shift triple fails to
+    // compile.
+    const VALID_SHIFTS: () = assert!(valid_shifts(A, B, C, 64),
+                                     "shifts must be in the range 1..64");
+
+    /// Construct the generator from its state, which can be done in a
+    /// `static`. An all-zero state is replaced, like in `from_seed`.
+    pub const fn new_unmixed(s0: u64, s1: u64) -> Self {
+        let () = Self::VALID_SHIFTS;
+        if s0 == 0 && s1 == 0 {
+            Self { s0: 0x0DD_B1A5E5_BAD_5EED, s1: 0x0DD_B1A5E5_BAD_5EED }
+        } else {
+            Self { s0, s1 }
+        }
+    }
 }
 
-impl fmt::Debug for Xorshift128_64Rng {
+impl<const A: u32, const B: u32, const C: u32> fmt::Debug
+    for Xorshift128_64Engine<A, B, C>
+{
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Xorshift128_64Rng {{}}")
+        write!(f, "Xorshift128_64Engine<{}, {}, {}> {{}}", A, B, C)
     }
 }
 
-impl SeedableRng for Xorshift128_64Rng {
+impl<const A: u32, const B: u32, const C: u32> SeedableRng
+    for Xorshift128_64Engine<A, B, C>
+{
     type Seed = [u8; 16];
 
     fn from_seed(seed: Self::Seed) -> Self {
+        let () = Self::VALID_SHIFTS;
         let mut seed_u64 = [0u64; 2];
         le::read_u64_into(&seed, &mut seed_u64);
 
@@ -135,7 +396,9 @@ impl SeedableRng for Xorshift128_64Rng {
     }
 }
 
-impl RngCore for Xorshift128_64Rng {
+impl<const A: u32, const B: u32, const C: u32> RngCore
+    for Xorshift128_64Engine<A, B, C>
+{
     #[inline]
     fn next_u32(&mut self) -> u32 {
         self.next_u64() as u32
@@ -145,8 +408,8 @@ impl RngCore for Xorshift128_64Rng {
     fn next_u64(&mut self) -> u64 {
         let x = self.s0;
         let y = self.s1;
-        let t = x ^ (x >> 18) ^ (y ^ (y >> 5));
-        self.s0 = y ^ (y << 23); // first xorshift step of the next round
+        let t = x ^ (x >> B) ^ (y ^ (y >> C));
+        self.s0 = y ^ (y << A); // first xorshift step of the next round
         self.s1 = t;
         t
     }
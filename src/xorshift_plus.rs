@@ -28,6 +28,18 @@ pub struct Xorshift128PlusRng {
     s1: u64,
 }
 
+impl Xorshift128PlusRng {
+    /// Construct the generator from its state, which can be done in a
+    /// `static`. An all-zero state is replaced, like in `from_seed`.
+    pub const fn new_unmixed(s0: u64, s1: u64) -> Self {
+        if s0 == 0 && s1 == 0 {
+            Self { s0: 0x0DD_B1A5E5_BAD_5EED, s1: 0x0DD_B1A5E5_BAD_5EED }
+        } else {
+            Self { s0, s1 }
+        }
+    }
+}
+
 impl SeedableRng for Xorshift128PlusRng {
     type Seed = [u8; 16];
 